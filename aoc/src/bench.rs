@@ -0,0 +1,150 @@
+use anyhow::Result;
+use common::Timings;
+use core::fmt;
+use serde::Serialize;
+use std::time::Duration;
+use toml::Table;
+
+use crate::days::Solver;
+
+/// The spread of one stage's times over repeated runs, in nanoseconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Stats {
+    pub min_ns: u128,
+    pub median_ns: u128,
+    pub max_ns: u128,
+}
+
+impl Stats {
+    /// Summarises `times`, which must not be empty.
+    pub fn new(times: &mut [Duration]) -> Self {
+        times.sort();
+        Stats {
+            min_ns: times[0].as_nanos(),
+            median_ns: times[times.len() / 2].as_nanos(),
+            max_ns: times[times.len() - 1].as_nanos(),
+        }
+    }
+}
+
+/// Times for each stage of one day.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DayBench {
+    pub day: u32,
+    pub runs: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+    pub total: Stats,
+}
+
+impl DayBench {
+    pub fn new(day: u32, timings: &[Timings]) -> Self {
+        let stats = |stage: fn(&Timings) -> Duration| {
+            Stats::new(&mut timings.iter().map(stage).collect::<Vec<_>>())
+        };
+        DayBench {
+            day,
+            runs: timings.len(),
+            parse: stats(|t| t.parse),
+            part1: stats(|t| t.part1),
+            part2: stats(|t| t.part2),
+            total: stats(|t| t.total()),
+        }
+    }
+}
+
+/// Solves `input` with `params` `runs` times (at least once), keeping the times of each run. The
+/// input is held in memory, so the times don't include reading it.
+pub fn bench(
+    day: u32,
+    solver: Solver,
+    input: &str,
+    params: &Table,
+    runs: usize,
+) -> Result<DayBench> {
+    let timings = (0..runs.max(1))
+        .map(|_| solver(&mut input.as_bytes(), params).map(|(_, timings)| timings))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(DayBench::new(day, &timings))
+}
+
+impl fmt::Display for DayBench {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ns = |n: u128| Duration::from_nanos(n as u64);
+        f.write_fmt(format_args!(
+            "--- Day {} ({} runs) ---\n",
+            self.day, self.runs
+        ))?;
+        f.write_fmt(format_args!(
+            "{:<8}{:>12}{:>12}{:>12}\n",
+            "", "min", "median", "max"
+        ))?;
+        for (name, stats) in [
+            ("parse", &self.parse),
+            ("part 1", &self.part1),
+            ("part 2", &self.part2),
+            ("total", &self.total),
+        ] {
+            f.write_fmt(format_args!(
+                "{:<8}{:>12.2?}{:>12.2?}{:>12.2?}\n",
+                name,
+                ns(stats.min_ns),
+                ns(stats.median_ns),
+                ns(stats.max_ns)
+            ))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn timings(parse: u64, part1: u64, part2: u64) -> Timings {
+        Timings {
+            parse: Duration::from_nanos(parse),
+            part1: Duration::from_nanos(part1),
+            part2: Duration::from_nanos(part2),
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut times = [5, 1, 3, 9, 7].map(Duration::from_nanos);
+        assert_eq!(
+            Stats::new(&mut times),
+            Stats {
+                min_ns: 1,
+                median_ns: 5,
+                max_ns: 9
+            }
+        );
+    }
+
+    #[test]
+    fn test_day_bench() {
+        let b = DayBench::new(
+            3,
+            &[
+                timings(10, 20, 30),
+                timings(12, 18, 30),
+                timings(11, 25, 40),
+            ],
+        );
+        assert_eq!(b.runs, 3);
+        assert_eq!(
+            (b.parse.min_ns, b.parse.median_ns, b.parse.max_ns),
+            (10, 11, 12)
+        );
+        assert_eq!((b.part1.min_ns, b.part1.max_ns), (18, 25));
+        assert_eq!(
+            (b.total.min_ns, b.total.median_ns, b.total.max_ns),
+            (60, 60, 76)
+        );
+
+        let json = serde_json::to_string(&b).unwrap();
+        assert!(json.starts_with(r#"{"day":3,"runs":3,"parse":{"min_ns":10,"#));
+    }
+}
@@ -0,0 +1,128 @@
+use anyhow::{anyhow, Context, Result};
+use std::{fs, io, path::Path};
+use toml::{Table, Value};
+
+/// Each day's parameters, from a TOML file with a table per day:
+///
+/// ```toml
+/// [day15]
+/// row = 10
+/// max = 20
+/// ```
+///
+/// A day's solver checks its own parameters, so mistakes in a day's table are only reported
+/// when that day runs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    days: Table,
+}
+
+/// The day a table is for, from its name.
+fn day_number(name: &str) -> Option<u32> {
+    name.strip_prefix("day")?.parse().ok()
+}
+
+impl Config {
+    pub fn parse(text: &str) -> Result<Self> {
+        let days = text.parse::<Table>()?;
+        for (name, params) in &days {
+            if day_number(name).is_none() {
+                return Err(anyhow!(
+                    "Expected a table per day, like [day7], got {}",
+                    name
+                ));
+            }
+            if !params.is_table() {
+                return Err(anyhow!("Expected {} to be a table of parameters", name));
+            }
+        }
+        Ok(Config { days })
+    }
+
+    /// Reads the config at `path`, which is empty if the file doesn't exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s).with_context(|| format!("Could not parse {}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(e).with_context(|| format!("Could not read {}", path.display())),
+        }
+    }
+
+    /// Overrides one parameter from a setting of the form "dayN.name=value". The value is read as
+    /// TOML if it can be, and as a string otherwise.
+    pub fn set(&mut self, setting: &str) -> Result<()> {
+        let bad = || anyhow!("Expected dayN.name=value, got {}", setting);
+        let (key, value) = setting.split_once('=').ok_or_else(bad)?;
+        let (day, name) = key.trim().split_once('.').ok_or_else(bad)?;
+        if day_number(day).is_none() || name.is_empty() {
+            return Err(bad());
+        }
+        let value = format!("value = {}", value)
+            .parse::<Table>()
+            .ok()
+            .and_then(|mut t| t.remove("value"))
+            .unwrap_or_else(|| Value::String(value.to_string()));
+
+        let params = self
+            .days
+            .entry(day)
+            .or_insert_with(|| Value::Table(Table::new()));
+        // Only tables get past parse
+        params
+            .as_table_mut()
+            .unwrap()
+            .insert(name.to_string(), value);
+        Ok(())
+    }
+
+    /// The parameters set for `day`, if any.
+    pub fn params(&self, day: u32) -> Table {
+        self.days
+            .iter()
+            .find(|(name, _)| day_number(name) == Some(day))
+            .and_then(|(_, params)| params.as_table())
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        let config = Config::parse("[day15]\nrow = 10\nmax = 20\n\n[day1]\ntop = 5\n")?;
+        assert_eq!(config.params(15).get("row"), Some(&Value::Integer(10)));
+        assert_eq!(config.params(1).get("top"), Some(&Value::Integer(5)));
+        assert!(config.params(7).is_empty());
+
+        assert!(Config::parse("row = 10").is_err());
+        assert!(Config::parse("[fifteen]\nrow = 10").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_set() -> Result<()> {
+        let mut config = Config::parse("[day15]\nrow = 10\nmax = 20\n")?;
+        config.set("day15.row=11")?;
+        config.set("day14.source=[499, 0]")?;
+        config.set("day9.name=not toml")?;
+
+        assert_eq!(config.params(15).get("row"), Some(&Value::Integer(11)));
+        assert_eq!(config.params(15).get("max"), Some(&Value::Integer(20)));
+        assert_eq!(
+            config.params(14).get("source"),
+            Some(&Value::Array(vec![Value::Integer(499), Value::Integer(0)]))
+        );
+        assert_eq!(
+            config.params(9).get("name"),
+            Some(&Value::String("not toml".to_string()))
+        );
+
+        assert!(config.set("day15.row").is_err());
+        assert!(config.set("row=10").is_err());
+        assert!(config.set("fifteen.row=10").is_err());
+        Ok(())
+    }
+}
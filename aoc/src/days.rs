@@ -0,0 +1,232 @@
+use anyhow::Context;
+use common::{
+    image::{Draw, Picture},
+    render::{Part, Player, Visualise},
+    Answers, Example, Solution, Timings,
+};
+use std::io::{BufRead, Write};
+use toml::{Table, Value};
+
+/// Solves a day from its input, with the parameters set for it; any left unset are the real
+/// puzzle's.
+pub type Solver = fn(&mut dyn BufRead, &Table) -> anyhow::Result<(Answers, Timings)>;
+
+/// Plays a day's working of one part from its input, with the parameters set for it.
+pub type Watcher =
+    fn(&mut dyn BufRead, &Table, Part, &mut Player<&mut dyn Write>) -> anyhow::Result<()>;
+
+/// Draws how one part of a day ends up, from its input, with the parameters set for it.
+pub type Drawer = fn(&mut dyn BufRead, &Table, Part) -> anyhow::Result<Picture>;
+
+/// What the runner knows about a day.
+pub struct Day {
+    pub day: u32,
+    pub solver: Solver,
+    pub examples: &'static [Example],
+}
+
+fn params<S: Solution>(params: &Table) -> anyhow::Result<S::Params> {
+    Value::Table(params.clone())
+        .try_into::<S::Params>()
+        .context("Invalid parameters")
+}
+
+fn solve<S: Solution>(
+    input: &mut dyn BufRead,
+    params: &Table,
+) -> anyhow::Result<(Answers, Timings)> {
+    S::solve_timed(input, &self::params::<S>(params)?)
+}
+
+fn watch<S: Visualise>(
+    input: &mut dyn BufRead,
+    params: &Table,
+    part: Part,
+    player: &mut Player<&mut dyn Write>,
+) -> anyhow::Result<()> {
+    let params = self::params::<S>(params)?;
+    let model = S::parse_reader_with(input, &params)?;
+    S::visualise(&model, &params, part, player)
+}
+
+const fn day<S: Solution>(day: u32) -> Day {
+    Day {
+        day,
+        solver: solve::<S>,
+        examples: S::EXAMPLES,
+    }
+}
+
+fn draw<S: Draw>(input: &mut dyn BufRead, params: &Table, part: Part) -> anyhow::Result<Picture> {
+    let params = self::params::<S>(params)?;
+    let model = S::parse_reader_with(input, &params)?;
+    S::draw(&model, &params, part)
+}
+
+/// Every day the runner knows about, in order.
+pub const DAYS: &[Day] = &[
+    day::<day_1::Day1>(1),
+    day::<day_2::Day2>(2),
+    day::<day_3::Day3>(3),
+    day::<day_4::Day4>(4),
+    day::<day_5::Day5>(5),
+    day::<day_6::Day6>(6),
+    day::<day_7::Day7>(7),
+    day::<day_8::Day8>(8),
+    day::<day_9::Day9>(9),
+    day::<day_10::Day10>(10),
+    day::<day_11::Day11>(11),
+    day::<day_12::Day12>(12),
+    day::<day_13::Day13>(13),
+    day::<day_14::Day14>(14),
+    day::<day_15::Day15>(15),
+];
+
+/// The days that can be watched a step at a time, with how to play them.
+pub const WATCHERS: &[(u32, Watcher)] = &[
+    (5, watch::<day_5::Day5>),
+    (9, watch::<day_9::Day9>),
+    (12, watch::<day_12::Day12>),
+    (14, watch::<day_14::Day14>),
+];
+
+/// The days that can be drawn as pictures, with how to draw them.
+pub const DRAWERS: &[(u32, Drawer)] = &[
+    (9, draw::<day_9::Day9>),
+    (14, draw::<day_14::Day14>),
+    (15, draw::<day_15::Day15>),
+];
+
+/// The parameters `example` is solved with.
+pub fn example_params(example: &Example) -> anyhow::Result<Table> {
+    example
+        .params
+        .parse()
+        .context("Invalid parameters for the example")
+}
+
+pub fn find(day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+pub fn solver(day: u32) -> Option<Solver> {
+    find(day).map(|d| d.solver)
+}
+
+pub fn watcher(day: u32) -> Option<Watcher> {
+    WATCHERS.iter().find(|(d, _)| *d == day).map(|(_, w)| *w)
+}
+
+pub fn drawer(day: u32) -> Option<Drawer> {
+    DRAWERS.iter().find(|(d, _)| *d == day).map(|(_, w)| *w)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() -> anyhow::Result<()> {
+        for day in DAYS {
+            for example in day.examples {
+                let params = example_params(example)?;
+                let (answers, _) = (day.solver)(&mut example.input.as_bytes(), &params)?;
+                for (expected, got) in [
+                    (example.part1, &answers.part1),
+                    (example.part2, &answers.part2),
+                ] {
+                    if let Some(expected) = expected {
+                        assert_eq!(got, expected, "day {}", day.day);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_watchers() -> anyhow::Result<()> {
+        for (day, watcher) in WATCHERS {
+            for example in find(*day).unwrap().examples {
+                for part in [Part::One, Part::Two] {
+                    let mut out = Vec::new();
+                    let mut player =
+                        Player::new(&mut out as &mut dyn Write, Default::default(), 0, true);
+                    watcher(
+                        &mut example.input.as_bytes(),
+                        &example_params(example)?,
+                        part,
+                        &mut player,
+                    )?;
+                    assert!(player.shown() > 0, "day {}", day);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_drawers() -> anyhow::Result<()> {
+        for (day, drawer) in DRAWERS {
+            for example in find(*day).unwrap().examples {
+                for part in [Part::One, Part::Two] {
+                    drawer(
+                        &mut example.input.as_bytes(),
+                        &example_params(example)?,
+                        part,
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_params() -> anyhow::Result<()> {
+        let solver = solver(1).unwrap();
+        let example = find(1).unwrap().examples[0].input;
+
+        let mut params = Table::new();
+        params.insert("top".to_string(), Value::Integer(2));
+        let (answers, _) = solver(&mut example.as_bytes(), &params)?;
+        assert_eq!(answers.part2, "35000");
+
+        params.insert("middle".to_string(), Value::Integer(2));
+        assert!(solver(&mut example.as_bytes(), &params).is_err());
+        let mut params = Table::new();
+        params.insert("top".to_string(), Value::String("two".to_string()));
+        assert!(solver(&mut example.as_bytes(), &params).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_day1_chunked() -> anyhow::Result<()> {
+        // Day 1 reads its input in chunks of 4MiB across threads, so make sure an input of more
+        // than one comes back together in order, with the line and elf an error is in counted
+        // from the start of the input
+        let solver = solver(1).unwrap();
+        let input = (1..=600000)
+            .map(|elf| format!("{}\n1", elf))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        assert!(input.len() > 1 << 22);
+        let mut params = Table::new();
+        params.insert("rank".to_string(), Value::Integer(600000));
+        let (answers, _) = solver(&mut input.as_bytes(), &params)?;
+        assert_eq!(answers.part1, "600001");
+        assert_eq!(answers.part2, (600001 + 600000 + 599999).to_string());
+        assert_eq!(
+            answers.details["rank"].to_string(),
+            r#"{"elf":0,"items":[1,1],"total":2}"#
+        );
+
+        let bad = format!("{}\n\nseven", input);
+        let e = solver(&mut bad.as_bytes(), &Table::new()).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "elf 600000, line 1800001, column 1: invalid number \"seven\""
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use crate::days::{self, Day};
+use common::{Answers, Example};
+use tracing::info_span;
+
+/// Shows one part's answer beside the example's expected one, and whether they match.
+fn describe(part: usize, expected: Option<&str>, got: &str) -> (String, bool) {
+    let multiline = got.contains('\n') || expected.is_some_and(|e| e.contains('\n'));
+    match expected {
+        None if multiline => (format!("Part {}:\n{}", part, got), true),
+        None => (format!("Part {}: {}", part, got), true),
+        Some(e) if e == got && multiline => (format!("Part {}: as expected\n{}", part, got), true),
+        Some(e) if e == got => (format!("Part {}: {} as expected", part, got), true),
+        Some(e) if multiline => (
+            format!("Part {}: MISMATCH, got\n{}\nexpected\n{}", part, got, e),
+            false,
+        ),
+        Some(e) => (
+            format!("Part {}: MISMATCH, got {}, expected {}", part, got, e),
+            false,
+        ),
+    }
+}
+
+/// Solves `example` with the parameters it asks for.
+pub fn solve(day: &Day, example: &Example) -> anyhow::Result<Answers> {
+    let params = days::example_params(example)?;
+    let (answers, _) = (day.solver)(&mut example.input.as_bytes(), &params)?;
+    Ok(answers)
+}
+
+/// Whether `answers` are the ones `example` expects, for the parts it has answers for.
+pub fn matches(example: &Example, answers: &Answers) -> bool {
+    [
+        (example.part1, &answers.part1),
+        (example.part2, &answers.part2),
+    ]
+    .iter()
+    .all(|(expected, got)| expected.is_none_or(|e| e == got.as_str()))
+}
+
+/// Solves each of `day`'s examples, printing the answers against the expected ones, and returns
+/// how many examples failed.
+pub fn check(day: &Day) -> usize {
+    if day.examples.is_empty() {
+        println!("--- Day {} ---", day.day);
+        println!("No examples");
+        return 0;
+    }
+
+    let mut failed = 0;
+    for (i, example) in day.examples.iter().enumerate() {
+        println!("--- Day {}, example {} ---", day.day, i + 1);
+        let _span = info_span!("example", day = day.day, example = i + 1).entered();
+        match solve(day, example) {
+            Ok(answers) => {
+                let mut ok = true;
+                for (part, expected, got) in [
+                    (1, example.part1, &answers.part1),
+                    (2, example.part2, &answers.part2),
+                ] {
+                    let (line, matched) = describe(part, expected, got);
+                    println!("{}", line);
+                    ok &= matched;
+                }
+                if !ok {
+                    failed += 1;
+                }
+            }
+            Err(e) => {
+                println!("{:#}", e);
+                failed += 1;
+            }
+        }
+    }
+    failed
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(
+            describe(1, Some("24000"), "24000"),
+            ("Part 1: 24000 as expected".to_string(), true)
+        );
+        assert_eq!(
+            describe(2, Some("45000"), "41000"),
+            (
+                "Part 2: MISMATCH, got 41000, expected 45000".to_string(),
+                false
+            )
+        );
+        assert_eq!(describe(1, None, "7"), ("Part 1: 7".to_string(), true));
+        assert_eq!(
+            describe(2, Some("#.\n.#"), "#.\n.#"),
+            ("Part 2: as expected\n#.\n.#".to_string(), true)
+        );
+        assert_eq!(
+            describe(2, Some("#.\n.#"), "#.\n##"),
+            (
+                "Part 2: MISMATCH, got\n#.\n##\nexpected\n#.\n.#".to_string(),
+                false
+            )
+        );
+    }
+
+    #[test]
+    fn test_matches() {
+        let example = Example::only_part1("1", "7");
+        assert!(matches(&example, &Answers::new(7, 0)));
+        assert!(!matches(&example, &Answers::new(8, 0)));
+        assert!(!matches(&Example::new("1", "7", "1"), &Answers::new(7, 0)));
+    }
+}
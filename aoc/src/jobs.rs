@@ -0,0 +1,73 @@
+use anyhow::{anyhow, Result};
+use rayon::prelude::*;
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+};
+
+/// What a panic was raised with, if it was a message.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "no message"
+    }
+}
+
+/// Runs `f` on every item, `jobs` at a time, returning the results in the items' order. A panic
+/// becomes that item's error, so the others still finish.
+pub fn run_all<T, R, F>(items: &[T], jobs: usize, f: F) -> Result<Vec<Result<R>>>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Result<R> + Sync,
+{
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(jobs).build()?;
+    Ok(pool.install(|| {
+        items
+            .par_iter()
+            .map(|item| {
+                panic::catch_unwind(AssertUnwindSafe(|| f(item))).unwrap_or_else(|payload| {
+                    Err(anyhow!("panicked: {}", panic_message(payload.as_ref())))
+                })
+            })
+            .collect()
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_run_all_in_order() -> Result<()> {
+        let items = (1..=20).collect::<Vec<u32>>();
+        let results = run_all(&items, 4, |i| Ok(i * 2))?;
+        let doubled = results.into_iter().collect::<Result<Vec<_>>>()?;
+        assert_eq!(doubled, (1..=20).map(|i| i * 2).collect::<Vec<_>>());
+        Ok(())
+    }
+
+    #[test]
+    fn test_run_all_isolates_failures() -> Result<()> {
+        let results = run_all(&[1, 2, 3, 4], 2, |i| match i {
+            2 => panic!("day {} exploded", i),
+            3 => Err(anyhow!("bad input")),
+            _ => Ok(*i),
+        })?;
+        let described = results
+            .iter()
+            .map(|r| match r {
+                Ok(i) => i.to_string(),
+                Err(e) => e.to_string(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            described,
+            ["1", "panicked: day 2 exploded", "bad input", "4"]
+        );
+        Ok(())
+    }
+}
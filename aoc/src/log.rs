@@ -0,0 +1,101 @@
+use anyhow::{anyhow, Result};
+use std::{
+    env,
+    io::{self, IsTerminal},
+};
+use tracing::Subscriber;
+use tracing_subscriber::{
+    fmt::{time::uptime, MakeWriter},
+    EnvFilter,
+};
+
+/// A subscriber writing what `filter` lets through to `writer`. The filter is a level, such as
+/// "debug", or levels for each crate, such as "day_12=trace,info".
+fn subscriber<W>(filter: &str, writer: W, ansi: bool) -> Result<impl Subscriber + Send + Sync>
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    let filter = EnvFilter::try_new(filter)
+        .map_err(|e| anyhow!("Invalid log filter {:?}: {}", filter, e))?;
+    Ok(tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer)
+        .with_ansi(ansi)
+        .with_timer(uptime())
+        .finish())
+}
+
+/// Logs to stderr, so diagnostics never mix with the answers on stdout.
+pub fn init(filter: &str) -> Result<()> {
+    let ansi = io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none();
+    tracing::subscriber::set_global_default(subscriber(filter, io::stderr, ansi)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use toml::Table;
+
+    /// Keeps everything written to it, to look at afterwards.
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl MakeWriter<'_> for Captured {
+        type Writer = Captured;
+
+        fn make_writer(&self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    impl Captured {
+        fn text(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    /// Solves the day's first example with `filter`, returning what was logged.
+    fn logged(day: u32, filter: &str) -> Result<String> {
+        let captured = Captured::default();
+        let example = crate::days::find(day).unwrap().examples[0].input;
+        let solver = crate::days::solver(day).unwrap();
+        tracing::subscriber::with_default(subscriber(filter, captured.clone(), false)?, || {
+            solver(&mut example.as_bytes(), &Table::new())
+        })?;
+        Ok(captured.text())
+    }
+
+    #[test]
+    fn test_levels() -> Result<()> {
+        let log = logged(11, "debug")?;
+        assert!(log.contains("part2: day_11: "));
+        assert!(log.contains("solved"));
+        assert!(!log.contains("TRACE"));
+
+        // Only the day asked about
+        let log = logged(11, "day_11=trace")?;
+        assert!(log.contains("TRACE"));
+        assert!(!log.contains("solved"));
+
+        assert_eq!(logged(11, "warn")?, "");
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_filter() {
+        assert!(subscriber("day_11=loud", io::sink, false).is_err());
+    }
+}
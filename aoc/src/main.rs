@@ -0,0 +1,667 @@
+mod bench;
+mod config;
+mod days;
+mod examples;
+mod jobs;
+mod log;
+mod report;
+mod scaffold;
+mod store;
+
+use anyhow::{anyhow, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use common::{
+    image::{self, FrameSequence},
+    render::{Part, Player, Viewport},
+    Answers, Timings,
+};
+use config::Config;
+use report::DayReport;
+use std::{
+    env,
+    fs::File,
+    io::{self, BufRead, BufReader, Read, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use store::{AnswerStore, Check};
+use toml::Table;
+use tracing::info_span;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2022 runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// File of accepted answers
+    #[arg(long, global = true, default_value = "answers.json")]
+    answers: PathBuf,
+
+    /// File of parameters for each day, such as [day15] row = 10
+    #[arg(long, global = true, default_value = "aoc.toml")]
+    config: PathBuf,
+
+    /// Set one day's parameter, overriding the config file
+    #[arg(long = "set", global = true, value_name = "dayN.NAME=VALUE")]
+    settings: Vec<String>,
+
+    /// Log diagnostics to stderr at this level, such as info or debug, or at levels for each
+    /// day, such as day_12=trace
+    #[arg(long, global = true, value_name = "FILTER")]
+    log: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one day, or all of them, and print both parts
+    Run {
+        /// Day number, or "all"
+        day: Selection,
+
+        /// Input file, or "-" for stdin [default: day-N/input]
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        /// How to print the answers; "json" adds each day's details and timings
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+
+        /// Accept the answers, recording them for `verify`
+        #[arg(long, conflicts_with = "input")]
+        save: bool,
+
+        /// Solve the puzzle's examples instead, checking the answers against the expected ones
+        #[arg(long, conflicts_with_all = ["input", "format", "save"])]
+        example: bool,
+
+        /// How many days to solve at once
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        jobs: u32,
+    },
+    /// Time parsing and each part over repeated runs
+    Bench {
+        /// Day number, or "all"
+        day: Selection,
+
+        /// Input file, or "-" for stdin [default: day-N/input]
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        /// How many times to solve each day
+        #[arg(short = 'n', long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        runs: u32,
+
+        /// Print the results as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Solve days against their real inputs and check the answers against the accepted ones
+    Verify {
+        /// Day number, or "all"
+        #[arg(default_value = "all")]
+        day: Selection,
+
+        /// How many days to solve at once
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        jobs: u32,
+    },
+    /// Watch a day being solved a step at a time in the terminal
+    Watch {
+        /// Day number
+        day: u32,
+
+        /// Input file, or "-" for stdin [default: day-N/input]
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        /// Watch the puzzle's example for the part instead
+        #[arg(long, conflicts_with = "input")]
+        example: bool,
+
+        /// Which part to watch
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Frames a second, or 0 for as fast as the terminal keeps up
+        #[arg(long, default_value_t = 10)]
+        fps: u32,
+
+        /// How much to show at once, as WIDTHxHEIGHT [default: $COLUMNS x $LINES, or 80x24]
+        #[arg(long)]
+        size: Option<Viewport>,
+
+        /// Draw without colour, as when NO_COLOR is set
+        #[arg(long)]
+        no_colour: bool,
+
+        /// Write each frame to this directory as a numbered PNG instead of showing it
+        #[arg(long, value_name = "DIR")]
+        frames: Option<PathBuf>,
+
+        /// How many pixels across each position of a written frame takes
+        #[arg(long, default_value_t = 4, requires = "frames", value_parser = clap::value_parser!(u32).range(1..))]
+        scale: u32,
+    },
+    /// Draw how one part of a day ends up, as a picture
+    Draw {
+        /// Day number
+        day: u32,
+
+        /// Input file, or "-" for stdin [default: day-N/input]
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        /// Draw the puzzle's example for the part instead
+        #[arg(long, conflicts_with = "input")]
+        example: bool,
+
+        /// Which part to draw
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Where to write the picture: a .png or .ppm for grids, or an .svg for shapes
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// How many pixels across each position of a grid takes
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..))]
+        scale: u32,
+    },
+    /// Generate a crate for a new day from the skeleton and register it with the runner
+    New {
+        /// Day number
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Selection {
+    All,
+    Day(u32),
+}
+
+impl FromStr for Selection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s == "all" {
+            return Ok(Selection::All);
+        }
+        let day = s
+            .parse()
+            .map_err(|_| anyhow!("Expected a day or \"all\", got {}", s))?;
+        if days::solver(day).is_none() {
+            return Err(anyhow!("Day {} is not implemented", day));
+        }
+        Ok(Selection::Day(day))
+    }
+}
+
+fn default_input(day: u32) -> PathBuf {
+    PathBuf::from(format!("day-{}", day)).join("input")
+}
+
+/// Opens the input, to be read a line at a time as it is parsed.
+fn open_input(path: &Path) -> Result<Box<dyn BufRead>> {
+    if path == Path::new("-") {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        let file =
+            File::open(path).with_context(|| format!("Could not read {}", path.display()))?;
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+/// The days picked by `selection`, each with the input to use.
+fn inputs(selection: Selection, input: Option<PathBuf>) -> Result<Vec<(u32, PathBuf)>> {
+    match selection {
+        Selection::Day(day) => Ok(vec![(day, input.unwrap_or_else(|| default_input(day)))]),
+        Selection::All => {
+            if input.is_some() {
+                return Err(anyhow!("--input can only be used with a single day"));
+            }
+            Ok(days::DAYS
+                .iter()
+                .map(|d| (d.day, default_input(d.day)))
+                .collect())
+        }
+    }
+}
+
+fn solve_day(day: u32, input: &Path, config: &Config) -> Result<(Answers, Timings)> {
+    let solver = days::solver(day).ok_or_else(|| anyhow!("Day {} is not implemented", day))?;
+    let _span = info_span!("day", day).entered();
+    let mut input = open_input(input)?;
+    solver(&mut input, &config.params(day)).with_context(|| format!("Day {} failed", day))
+}
+
+/// Solves each day against its input, `jobs` days at a time, returning the results in order.
+fn solve_days(
+    inputs: &[(u32, PathBuf)],
+    config: &Config,
+    jobs: u32,
+) -> Result<Vec<Result<(Answers, Timings)>>> {
+    jobs::run_all(inputs, jobs as usize, |(day, input)| {
+        solve_day(*day, input, config)
+    })
+}
+
+fn run(
+    selection: Selection,
+    input: Option<PathBuf>,
+    format: Format,
+    save: Option<&Path>,
+    config: &Config,
+    jobs: u32,
+) -> Result<()> {
+    let mut store = match save {
+        Some(path) => Some(AnswerStore::load(path)?),
+        None => None,
+    };
+
+    let inputs = inputs(selection, input)?;
+    if save.is_some() {
+        // Only the real puzzle's answers are worth accepting
+        if let Some((day, _)) = inputs.iter().find(|(d, _)| !config.params(*d).is_empty()) {
+            return Err(anyhow!(
+                "Day {} has parameters set, so its answers can't be saved",
+                day
+            ));
+        }
+    }
+    let mut reports = Vec::new();
+    let results = solve_days(&inputs, config, jobs)?;
+    let mut failed = 0;
+    for ((day, _), result) in inputs.iter().zip(results) {
+        match result {
+            Ok((answers, timings)) => {
+                if let Some(store) = &mut store {
+                    store.record(*day, &answers);
+                }
+                match format {
+                    Format::Text => {
+                        println!("--- Day {} ---", day);
+                        print!("{}", answers);
+                    }
+                    Format::Json => reports.push(DayReport::solved(*day, &answers, &timings)),
+                }
+            }
+            Err(e) if format == Format::Json => {
+                reports.push(DayReport::failed(*day, &e));
+                failed += 1;
+            }
+            Err(e) if inputs.len() > 1 => {
+                eprintln!("{:#}", e);
+                failed += 1;
+            }
+            Err(e) => return Err(e),
+        }
+        if format == Format::Text && inputs.len() > 1 {
+            println!();
+        }
+    }
+
+    if format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    }
+    if let (Some(store), Some(path)) = (store, save) {
+        store.save(path)?;
+    }
+    if failed > 0 {
+        Err(anyhow!("{} days failed", failed))
+    } else {
+        Ok(())
+    }
+}
+
+/// The days picked by `selection`.
+fn selected_days(selection: Selection) -> Result<Vec<&'static days::Day>> {
+    match selection {
+        Selection::All => Ok(days::DAYS.iter().collect()),
+        Selection::Day(day) => {
+            Ok(vec![days::find(day).ok_or_else(|| {
+                anyhow!("Day {} is not implemented", day)
+            })?])
+        }
+    }
+}
+
+fn run_examples(selection: Selection) -> Result<()> {
+    let days = selected_days(selection)?;
+    let mut failed = 0;
+    for (i, day) in days.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        failed += examples::check(day);
+    }
+    if failed > 0 {
+        Err(anyhow!("{} examples failed", failed))
+    } else {
+        Ok(())
+    }
+}
+
+fn bench(
+    selection: Selection,
+    input: Option<PathBuf>,
+    config: &Config,
+    runs: u32,
+    json: bool,
+) -> Result<()> {
+    let mut results = Vec::new();
+    for (day, input) in inputs(selection, input)? {
+        let solver = days::solver(day).ok_or_else(|| anyhow!("Day {} is not implemented", day))?;
+        let _span = info_span!("day", day).entered();
+        let mut text = String::new();
+        open_input(&input)?
+            .read_to_string(&mut text)
+            .with_context(|| format!("Could not read {}", input.display()))?;
+        let result = bench::bench(day, solver, &text, &config.params(day), runs as usize)
+            .with_context(|| format!("Day {} failed", day))?;
+        if !json {
+            println!("{}", result);
+        }
+        results.push(result);
+    }
+    if json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+    }
+    Ok(())
+}
+
+/// Describes how a day's answers compare with the accepted ones; empty if they all match.
+fn describe_checks(checks: &[Check; 2], answers: &Answers) -> Vec<String> {
+    if checks.iter().all(|c| *c == Check::Missing) {
+        return vec!["no accepted answers".to_string()];
+    }
+    let got = [&answers.part1, &answers.part2];
+    checks
+        .iter()
+        .zip(got)
+        .enumerate()
+        .filter_map(|(i, (check, got))| match check {
+            Check::Match => None,
+            Check::Missing => Some(format!("part {} has no accepted answer", i + 1)),
+            Check::Mismatch { expected } => Some(format!(
+                "part {} expected {:?}, got {:?}",
+                i + 1,
+                expected,
+                got
+            )),
+        })
+        .collect()
+}
+
+/// Checks the real puzzle's answers, so ignores any parameters that are set, and that the
+/// examples still give theirs.
+fn verify(selection: Selection, answers: &Path, jobs: u32) -> Result<()> {
+    let store = AnswerStore::load(answers)?;
+
+    let inputs = inputs(selection, None)?;
+    let results = solve_days(&inputs, &Config::default(), jobs)?;
+    let (mut mismatched, mut missing, mut failed) = (0, 0, 0);
+    for ((day, _), result) in inputs.iter().zip(results) {
+        let day = *day;
+        match result {
+            Ok((answers, timings)) => {
+                let checks = store.check(day, &answers);
+                let problems = describe_checks(&checks, &answers);
+                let status = if problems.is_empty() {
+                    "ok".to_string()
+                } else {
+                    problems.join("; ")
+                };
+                println!("Day {:>2}  {:>10.2?}  {}", day, timings.total(), status);
+
+                if checks.iter().any(|c| matches!(c, Check::Mismatch { .. })) {
+                    mismatched += 1;
+                } else if checks.contains(&Check::Missing) {
+                    missing += 1;
+                }
+            }
+            Err(e) => {
+                println!("Day {:>2}  {:>10}  {:#}", day, "-", e);
+                failed += 1;
+            }
+        }
+    }
+
+    // Only the examples that go wrong are worth a line
+    for day in selected_days(selection)? {
+        for (i, example) in day.examples.iter().enumerate() {
+            match examples::solve(day, example) {
+                Ok(answers) if examples::matches(example, &answers) => {}
+                Ok(answers) => {
+                    println!(
+                        "Day {:>2}  example {}: MISMATCH, got {} and {}",
+                        day.day,
+                        i + 1,
+                        answers.part1,
+                        answers.part2
+                    );
+                    mismatched += 1;
+                }
+                Err(e) => {
+                    println!("Day {:>2}  example {}: {:#}", day.day, i + 1, e);
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "{} mismatched, {} missing, {} failed",
+        mismatched, missing, failed
+    );
+    if mismatched + failed > 0 {
+        Err(anyhow!("Verification failed"))
+    } else {
+        Ok(())
+    }
+}
+
+/// How to show a day's frames.
+struct Screen {
+    fps: u32,
+    size: Option<Viewport>,
+    colour: bool,
+    /// Where to write the frames as pictures instead, and how many pixels across each
+    /// position takes
+    frames: Option<(PathBuf, u32)>,
+}
+
+/// The terminal's size, if the shell exported it.
+fn terminal_size() -> Option<Viewport> {
+    let get = |name| env::var(name).ok()?.parse().ok().filter(|n| *n > 0);
+    Some(Viewport::new(get("COLUMNS")?, get("LINES")?))
+}
+
+/// The error for a day that can't be `what`, listing the ones in `days` that can.
+fn unsupported<T>(day: u32, what: &str, days: &[(u32, T)]) -> anyhow::Error {
+    let days = days.iter().map(|(d, _)| d.to_string()).collect::<Vec<_>>();
+    anyhow!(
+        "Day {} can't be {}, only days {}",
+        day,
+        what,
+        days.join(", ")
+    )
+}
+
+/// The input to play one part from, and the parameters to play it with: the input file, or the
+/// first of the day's examples with an answer for that part, with the example's parameters in
+/// place of any set for the day.
+fn part_input(
+    day: u32,
+    input: Option<PathBuf>,
+    example: bool,
+    part: Part,
+    config: &Config,
+) -> Result<(Box<dyn BufRead>, Table)> {
+    let mut params = config.params(day);
+    if !example {
+        let input = open_input(&input.unwrap_or_else(|| default_input(day)))?;
+        return Ok((input, params));
+    }
+    let examples = days::find(day).map(|d| d.examples).unwrap_or_default();
+    let example = examples
+        .iter()
+        .find(|e| match part {
+            Part::One => e.part1.is_some(),
+            Part::Two => e.part2.is_some(),
+        })
+        .ok_or_else(|| anyhow!("Day {} has no example for that part", day))?;
+    params.extend(days::example_params(example)?);
+    Ok((Box::new(example.input.as_bytes()), params))
+}
+
+fn watch(
+    day: u32,
+    input: Option<PathBuf>,
+    example: bool,
+    part: Part,
+    config: &Config,
+    screen: Screen,
+) -> Result<()> {
+    let watcher = days::watcher(day).ok_or_else(|| unsupported(day, "watched", days::WATCHERS))?;
+    let _span = info_span!("day", day).entered();
+    let (mut input, params) = part_input(day, input, example, part, config)?;
+
+    let viewport = screen.size.or_else(terminal_size).unwrap_or_default();
+    let (mut stdout, mut sink) = (io::stdout().lock(), io::sink());
+    let mut player = match &screen.frames {
+        // Recording, so there's nothing to watch and no need to wait between frames
+        Some((dir, scale)) => {
+            let frames = FrameSequence::new(dir, image::Format::Png, *scale as usize)
+                .with_context(|| format!("Could not write to {}", dir.display()))?;
+            Player::new(&mut sink as &mut dyn Write, viewport, 0, false).record(frames)
+        }
+        None => Player::new(
+            &mut stdout as &mut dyn Write,
+            viewport,
+            screen.fps,
+            screen.colour,
+        ),
+    };
+    watcher(&mut input, &params, part, &mut player)
+        .with_context(|| format!("Day {} failed", day))?;
+    if let Some((dir, _)) = &screen.frames {
+        println!("Wrote {} frames to {}", player.shown(), dir.display());
+    }
+    Ok(())
+}
+
+fn draw(
+    day: u32,
+    input: Option<PathBuf>,
+    example: bool,
+    part: Part,
+    config: &Config,
+    output: &Path,
+    scale: u32,
+) -> Result<()> {
+    let drawer = days::drawer(day).ok_or_else(|| unsupported(day, "drawn", days::DRAWERS))?;
+    let _span = info_span!("day", day).entered();
+    let (mut input, params) = part_input(day, input, example, part, config)?;
+    let picture =
+        drawer(&mut input, &params, part).with_context(|| format!("Day {} failed", day))?;
+    picture.save(output, scale as usize)
+}
+
+fn to_part(part: u8) -> Part {
+    if part == 1 {
+        Part::One
+    } else {
+        Part::Two
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    if let Some(filter) = &cli.log {
+        log::init(filter)?;
+    }
+    let mut config = Config::load(&cli.config)?;
+    for setting in &cli.settings {
+        config.set(setting)?;
+    }
+
+    match cli.command {
+        Command::Run {
+            day,
+            input,
+            format,
+            save,
+            example,
+            jobs,
+        } => {
+            if example {
+                run_examples(day)
+            } else {
+                run(
+                    day,
+                    input,
+                    format,
+                    save.then_some(cli.answers.as_path()),
+                    &config,
+                    jobs,
+                )
+            }
+        }
+        Command::Bench {
+            day,
+            input,
+            runs,
+            json,
+        } => bench(day, input, &config, runs, json),
+        Command::Verify { day, jobs } => verify(day, &cli.answers, jobs),
+        Command::Watch {
+            day,
+            input,
+            example,
+            part,
+            fps,
+            size,
+            no_colour,
+            frames,
+            scale,
+        } => {
+            let screen = Screen {
+                fps,
+                size,
+                colour: !no_colour && env::var_os("NO_COLOR").is_none(),
+                frames: frames.map(|dir| (dir, scale)),
+            };
+            watch(day, input, example, to_part(part), &config, screen)
+        }
+        Command::Draw {
+            day,
+            input,
+            example,
+            part,
+            output,
+            scale,
+        } => {
+            draw(day, input, example, to_part(part), &config, &output, scale)?;
+            println!("Drew day {} part {} to {}", day, part, output.display());
+            Ok(())
+        }
+        Command::New { day } => {
+            scaffold::new_day(Path::new("."), day)?;
+            println!(
+                "Created day-{}; save the puzzle input as day-{}/input",
+                day, day
+            );
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use common::{Answers, Details, Timings};
+use serde::Serialize;
+
+/// How long each stage took, in nanoseconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct TimingsReport {
+    pub parse_ns: u128,
+    pub part1_ns: u128,
+    pub part2_ns: u128,
+    pub total_ns: u128,
+}
+
+impl From<&Timings> for TimingsReport {
+    fn from(t: &Timings) -> Self {
+        TimingsReport {
+            parse_ns: t.parse.as_nanos(),
+            part1_ns: t.part1.as_nanos(),
+            part2_ns: t.part2.as_nanos(),
+            total_ns: t.total().as_nanos(),
+        }
+    }
+}
+
+/// One day's result as reported by `run --format json`: either the answers with their details
+/// and timings, or the error that stopped it.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DayReport {
+    pub day: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Details>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timings: Option<TimingsReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl DayReport {
+    pub fn solved(day: u32, answers: &Answers, timings: &Timings) -> Self {
+        DayReport {
+            day,
+            part1: Some(answers.part1.clone()),
+            part2: Some(answers.part2.clone()),
+            details: Some(answers.details.clone()),
+            timings: Some(timings.into()),
+            error: None,
+        }
+    }
+
+    pub fn failed(day: u32, error: &anyhow::Error) -> Self {
+        DayReport {
+            day,
+            part1: None,
+            part2: None,
+            details: None,
+            timings: None,
+            error: Some(format!("{:#}", error)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_json() -> anyhow::Result<()> {
+        let mut answers = Answers::new(24000, 45000);
+        answers
+            .details
+            .insert("top_three".to_string(), vec![24000, 11000, 10000].into());
+        let timings = Timings {
+            parse: Duration::from_nanos(100),
+            part1: Duration::from_nanos(20),
+            part2: Duration::from_nanos(30),
+        };
+
+        let json = serde_json::to_string(&DayReport::solved(1, &answers, &timings))?;
+        assert_eq!(
+            json,
+            r#"{"day":1,"part1":"24000","part2":"45000","details":{"top_three":[24000,11000,10000]},"timings":{"parse_ns":100,"part1_ns":20,"part2_ns":30,"total_ns":150}}"#
+        );
+
+        let error = anyhow::anyhow!("line 2: invalid number \"x\"");
+        let json = serde_json::to_string(&DayReport::failed(4, &error))?;
+        assert_eq!(json, r#"{"day":4,"error":"line 2: invalid number \"x\""}"#);
+        Ok(())
+    }
+}
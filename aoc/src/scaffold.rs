@@ -0,0 +1,163 @@
+use anyhow::{anyhow, Context, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const CARGO_TOML: &str = include_str!("../../skeleton/Cargo.toml");
+const MAIN_RS: &str = include_str!("../../skeleton/src/main.rs");
+const LIB_RS: &str = include_str!("../../skeleton/src/lib.rs");
+
+/// Fills in the skeleton crate's names for `day`.
+fn render(template: &str, day: u32) -> String {
+    template
+        .replace("name = \"skeleton\"", &format!("name = \"day-{}\"", day))
+        .replace("Skeleton", &format!("Day{}", day))
+        .replace("skeleton", &format!("day_{}", day))
+}
+
+/// Inserts `new` among the lines that `number` picks out, keeping them in day order.
+fn insert_line(text: &str, day: u32, new: &str, number: fn(&str) -> Option<u32>) -> Result<String> {
+    let mut lines = text.lines().collect::<Vec<_>>();
+    let mut at = None;
+    for (i, line) in lines.iter().enumerate() {
+        match number(line) {
+            Some(n) if n == day => return Err(anyhow!("Day {} is already registered", day)),
+            Some(n) if n > day => {
+                at = Some(i);
+                break;
+            }
+            Some(_) => at = Some(i + 1),
+            None => (),
+        }
+    }
+    let at = at.ok_or_else(|| anyhow!("No days found to register day {} alongside", day))?;
+    lines.insert(at, new);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds `day` to the workspace members.
+fn register_member(workspace: &str, day: u32) -> Result<String> {
+    insert_line(workspace, day, &format!("    \"day-{}\",", day), |line| {
+        line.trim()
+            .strip_prefix("\"day-")?
+            .strip_suffix("\",")?
+            .parse()
+            .ok()
+    })
+}
+
+/// Adds `day` to the runner's dependencies.
+fn register_dependency(manifest: &str, day: u32) -> Result<String> {
+    let new = format!("day-{} = {{ path = \"../day-{}\" }}", day, day);
+    insert_line(manifest, day, &new, |line| {
+        line.strip_prefix("day-")?.split_once(' ')?.0.parse().ok()
+    })
+}
+
+/// Adds `day` to the runner's list of solvers.
+fn register_solver(days: &str, day: u32) -> Result<String> {
+    let new = format!("    day::<day_{}::Day{}>({}),", day, day, day);
+    insert_line(days, day, &new, |line| {
+        line.trim()
+            .strip_prefix("day::<day_")?
+            .split_once("::")?
+            .0
+            .parse()
+            .ok()
+    })
+}
+
+type Edit = fn(&str, u32) -> Result<String>;
+
+/// Generates a crate for `day` from the skeleton under `root`, and registers it with the
+/// workspace and the runner.
+pub fn new_day(root: &Path, day: u32) -> Result<()> {
+    let dir = root.join(format!("day-{}", day));
+    if dir.exists() {
+        return Err(anyhow!("{} already exists", dir.display()));
+    }
+
+    // Make every edit before writing anything, so a failure leaves the tree as it was
+    let edits: [(PathBuf, Edit); 3] = [
+        (root.join("Cargo.toml"), register_member),
+        (root.join("aoc").join("Cargo.toml"), register_dependency),
+        (
+            root.join("aoc").join("src").join("days.rs"),
+            register_solver,
+        ),
+    ];
+    let mut updated = Vec::new();
+    for (path, edit) in edits {
+        let text = fs::read_to_string(&path).with_context(|| {
+            format!(
+                "Could not read {}; is this the workspace root?",
+                path.display()
+            )
+        })?;
+        let text =
+            edit(&text, day).with_context(|| format!("Could not update {}", path.display()))?;
+        updated.push((path, text));
+    }
+
+    fs::create_dir_all(dir.join("src"))?;
+    fs::write(dir.join("Cargo.toml"), render(CARGO_TOML, day))?;
+    fs::write(dir.join("src").join("main.rs"), render(MAIN_RS, day))?;
+    fs::write(dir.join("src").join("lib.rs"), render(LIB_RS, day))?;
+    for (path, text) in updated {
+        fs::write(&path, text).with_context(|| format!("Could not write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let manifest = render(CARGO_TOML, 16);
+        assert!(manifest.contains("name = \"day-16\""));
+        assert!(manifest.contains("common = { path = \"../common\" }"));
+
+        let main = render(MAIN_RS, 16);
+        assert!(main.contains("day_16::Day16::solve(&input)"));
+
+        let lib = render(LIB_RS, 16);
+        assert!(lib.contains("pub struct Day16;"));
+        assert!(lib.contains("impl Solution for Day16"));
+        assert!(lib.contains("Day16::solve(EXAMPLE)"));
+        assert!(!lib.contains("keleton"));
+    }
+
+    #[test]
+    fn test_register_in_order() -> Result<()> {
+        let workspace = "members = [\n    \"aoc\",\n    \"day-1\",\n    \"day-3\",\n]\n";
+        assert_eq!(
+            register_member(workspace, 2)?,
+            "members = [\n    \"aoc\",\n    \"day-1\",\n    \"day-2\",\n    \"day-3\",\n]\n"
+        );
+        assert_eq!(
+            register_member(workspace, 4)?,
+            "members = [\n    \"aoc\",\n    \"day-1\",\n    \"day-3\",\n    \"day-4\",\n]\n"
+        );
+        assert!(register_member(workspace, 3).is_err());
+        assert!(register_member("members = []\n", 1).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_real_files() -> Result<()> {
+        let workspace = register_member(include_str!("../../Cargo.toml"), 99)?;
+        assert!(workspace.contains("    \"day-99\",\n]"));
+
+        let manifest = register_dependency(include_str!("../Cargo.toml"), 99)?;
+        assert!(manifest.contains("\nday-99 = { path = \"../day-99\" }\n"));
+        assert!(register_dependency(include_str!("../Cargo.toml"), 1).is_err());
+
+        let days = register_solver(include_str!("days.rs"), 99)?;
+        assert!(days.contains("    day::<day_99::Day99>(99),\n];"));
+        assert!(register_solver(include_str!("days.rs"), 12).is_err());
+        Ok(())
+    }
+}
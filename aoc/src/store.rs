@@ -0,0 +1,124 @@
+use anyhow::{Context, Result};
+use common::Answers;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+/// A day's accepted answers. Either part may not have been accepted yet.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Expected {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+/// How one part's answer compares with the accepted one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    Match,
+    Mismatch { expected: String },
+    Missing,
+}
+
+/// The accepted answers for every day, kept in a JSON file keyed by day number, so that later
+/// runs against the real inputs can be checked against them.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AnswerStore {
+    days: BTreeMap<u32, Expected>,
+}
+
+fn check(expected: &Option<String>, answer: &str) -> Check {
+    match expected {
+        None => Check::Missing,
+        Some(e) if e == answer => Check::Match,
+        Some(e) => Check::Mismatch {
+            expected: e.clone(),
+        },
+    }
+}
+
+impl AnswerStore {
+    /// Reads the store at `path`, which is empty if the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(s) => serde_json::from_str(&s)
+                .with_context(|| format!("Could not parse {}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(AnswerStore::default()),
+            Err(e) => Err(e).with_context(|| format!("Could not read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+        fs::write(path, json).with_context(|| format!("Could not write {}", path.display()))
+    }
+
+    /// Accepts both of `answers` for `day`, replacing whatever was there.
+    pub fn record(&mut self, day: u32, answers: &Answers) {
+        self.days.insert(
+            day,
+            Expected {
+                part1: Some(answers.part1.clone()),
+                part2: Some(answers.part2.clone()),
+            },
+        );
+    }
+
+    /// Compares both parts of `answers` with the accepted ones for `day`.
+    pub fn check(&self, day: u32, answers: &Answers) -> [Check; 2] {
+        let expected = self.days.get(&day).cloned().unwrap_or_default();
+        [
+            check(&expected.part1, &answers.part1),
+            check(&expected.part2, &answers.part2),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let mut store = AnswerStore::default();
+        let answers = Answers::new(24000, 45000);
+        assert_eq!(store.check(1, &answers), [Check::Missing, Check::Missing]);
+
+        store.record(1, &answers);
+        assert_eq!(store.check(1, &answers), [Check::Match, Check::Match]);
+        assert_eq!(
+            store.check(1, &Answers::new(24000, 41000)),
+            [
+                Check::Match,
+                Check::Mismatch {
+                    expected: "45000".to_string()
+                }
+            ]
+        );
+        assert_eq!(store.check(2, &answers), [Check::Missing, Check::Missing]);
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<()> {
+        let mut store = AnswerStore::default();
+        store.record(10, &Answers::new(13140, "##..\n..##"));
+        store.record(2, &Answers::new(15, 12));
+
+        let json = serde_json::to_string(&store)?;
+        assert_eq!(
+            json,
+            r###"{"2":{"part1":"15","part2":"12"},"10":{"part1":"13140","part2":"##..\n..##"}}"###
+        );
+        assert_eq!(serde_json::from_str::<AnswerStore>(&json)?, store);
+
+        // A part that hasn't been accepted can be left out
+        let partial: AnswerStore = serde_json::from_str(r#"{"11":{"part2":"2713310158"}}"#)?;
+        assert_eq!(
+            partial.check(11, &Answers::new(10605, 2713310158u64)),
+            [Check::Missing, Check::Match]
+        );
+        Ok(())
+    }
+}
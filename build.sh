@@ -2,10 +2,6 @@
 
 set -e
 
-for build in day*; do
-    pushd $build
-    cargo build
-    cargo test
-    cargo clippy -- -Dwarnings
-    popd
-done;
\ No newline at end of file
+cargo build --workspace
+cargo test --workspace
+cargo clippy --workspace --all-targets -- -Dwarnings
@@ -0,0 +1,58 @@
+use core::fmt;
+use std::fmt::Write;
+
+/// Named extra results that explain a day's answers, e.g. the values they were built from.
+pub type Details = serde_json::Map<String, serde_json::Value>;
+
+/// A day's answers, ready for printing.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    pub part1: String,
+    pub part2: String,
+    pub details: Details,
+}
+
+impl Answers {
+    pub fn new<P1, P2>(part1: P1, part2: P2) -> Self
+    where
+        P1: ToString,
+        P2: ToString,
+    {
+        Answers {
+            part1: part1.to_string(),
+            part2: part2.to_string(),
+            details: Details::new(),
+        }
+    }
+}
+
+fn write_part(f: &mut fmt::Formatter<'_>, part: usize, answer: &str) -> fmt::Result {
+    // Multi-line answers (e.g. the day 10 CRT) start on their own line
+    if answer.contains('\n') {
+        f.write_fmt(format_args!("Part {}:\n{}", part, answer))?;
+    } else {
+        f.write_fmt(format_args!("Part {}: {}", part, answer))?;
+    }
+    f.write_char('\n')
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_part(f, 1, &self.part1)?;
+        write_part(f, 2, &self.part2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let a = Answers::new(24000, 45000);
+        assert_eq!(a.to_string(), "Part 1: 24000\nPart 2: 45000\n");
+
+        let a = Answers::new("CMZ", "#.\n.#");
+        assert_eq!(a.to_string(), "Part 1: CMZ\nPart 2:\n#.\n.#\n");
+    }
+}
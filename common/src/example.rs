@@ -0,0 +1,46 @@
+/// A puzzle example and the answers the puzzle gives for it. Some examples only illustrate one
+/// of the parts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub input: &'static str,
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+    /// The parameters the example is solved with instead of the real puzzle's, as a TOML
+    /// table. Empty for most examples.
+    pub params: &'static str,
+}
+
+impl Example {
+    pub const fn new(input: &'static str, part1: &'static str, part2: &'static str) -> Self {
+        Example {
+            input,
+            part1: Some(part1),
+            part2: Some(part2),
+            params: "",
+        }
+    }
+
+    pub const fn only_part1(input: &'static str, part1: &'static str) -> Self {
+        Example {
+            input,
+            part1: Some(part1),
+            part2: None,
+            params: "",
+        }
+    }
+
+    pub const fn only_part2(input: &'static str, part2: &'static str) -> Self {
+        Example {
+            input,
+            part1: None,
+            part2: Some(part2),
+            params: "",
+        }
+    }
+
+    /// The same example, asking about something other than the real puzzle does, such as
+    /// `"row = 10"`.
+    pub const fn with_params(self, params: &'static str) -> Self {
+        Example { params, ..self }
+    }
+}
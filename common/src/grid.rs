@@ -0,0 +1,324 @@
+use core::fmt;
+use std::{
+    fmt::Write,
+    iter,
+    ops::{Index, IndexMut},
+};
+
+use anyhow::{anyhow, Result};
+
+use crate::{ParseError, ParseErrorKind, Vec2};
+
+/// A rectangular grid of cells stored row-major in one `Vec`.
+///
+/// Cells are addressed by `Vec2`, with `y` increasing down the rows. The grid doesn't have to
+/// start at (0, 0): `origin` is the position of the top-left cell, so (for example) day 14's
+/// cave can be indexed with the coordinates straight from the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    origin: Vec2,
+    size: Vec2,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(origin: Vec2, size: Vec2, fill: T) -> Self {
+        Grid {
+            origin,
+            size,
+            cells: vec![fill; (size.x * size.y) as usize],
+        }
+    }
+
+    /// A grid covering both corners (inclusive).
+    pub fn from_corners(top_left: Vec2, bottom_right: Vec2, fill: T) -> Self {
+        Grid::new(top_left, bottom_right - top_left + Vec2::new(1, 1), fill)
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn from_vec(origin: Vec2, size: Vec2, cells: Vec<T>) -> Result<Self> {
+        if size.x < 0 || size.y < 0 || cells.len() != (size.x * size.y) as usize {
+            return Err(anyhow!("{} cells do not fill a {} grid", cells.len(), size));
+        }
+        Ok(Grid {
+            origin,
+            size,
+            cells,
+        })
+    }
+
+    /// Parses a block of text with one cell per character, mapping each one with `f`, which
+    /// returns `None` for characters that aren't allowed. The first character of the first line
+    /// is at (0, 0).
+    pub fn parse<F>(input: &str, f: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        Self::parse_lines(input.lines(), f)
+    }
+
+    /// Like `parse`, but reads the grid a line at a time.
+    pub fn parse_lines<I, S, F>(lines: I, mut f: F) -> Result<Self, ParseError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut cells = Vec::new();
+        let mut size = Vec2::origin();
+        for line in lines {
+            let line = line.as_ref();
+            let at = |column: usize, text: &str, kind| ParseError {
+                line: size.y as usize + 1,
+                column,
+                text: text.to_string(),
+                kind,
+            };
+            let width = line.chars().count() as isize;
+            if size.y == 0 {
+                size.x = width;
+            } else if width != size.x {
+                return Err(at(
+                    0,
+                    line,
+                    ParseErrorKind::Invalid(format!("line is {} wide, expected {}", width, size.x)),
+                ));
+            }
+            for (i, c) in line.chars().enumerate() {
+                let cell = f(c).ok_or_else(|| {
+                    at(
+                        i + 1,
+                        c.encode_utf8(&mut [0; 4]),
+                        ParseErrorKind::Unexpected,
+                    )
+                })?;
+                cells.push(cell);
+            }
+            size.y += 1;
+        }
+        Ok(Grid {
+            origin: Vec2::origin(),
+            size,
+            cells,
+        })
+    }
+
+    pub fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    pub fn width(&self) -> usize {
+        self.size.x as usize
+    }
+
+    pub fn height(&self) -> usize {
+        self.size.y as usize
+    }
+
+    pub fn in_bounds(&self, p: Vec2) -> bool {
+        p.x >= self.origin.x
+            && p.x < self.origin.x + self.size.x
+            && p.y >= self.origin.y
+            && p.y < self.origin.y + self.size.y
+    }
+
+    fn offset(&self, p: Vec2) -> Option<usize> {
+        if self.in_bounds(p) {
+            let v = p - self.origin;
+            Some((v.x + v.y * self.size.x) as usize)
+        } else {
+            None
+        }
+    }
+
+    fn position(&self, offset: usize) -> Vec2 {
+        let offset = offset as isize;
+        self.origin + Vec2::new(offset % self.size.x, offset / self.size.x)
+    }
+
+    pub fn get(&self, p: Vec2) -> Option<&T> {
+        self.offset(p).map(|o| &self.cells[o])
+    }
+
+    pub fn get_mut(&mut self, p: Vec2) -> Option<&mut T> {
+        self.offset(p).map(|o| &mut self.cells[o])
+    }
+
+    /// Every position in the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Vec2> + '_ {
+        (0..self.cells.len()).map(|o| self.position(o))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(o, c)| (self.position(o), c))
+    }
+
+    /// Each row as a slice, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks() panics on zero, and an empty grid has no rows anyway
+        self.cells.chunks(self.width().max(1))
+    }
+
+    /// The cells of row `y`, left to right.
+    pub fn row(&self, y: isize) -> impl Iterator<Item = (Vec2, &T)> {
+        self.ray(Vec2::new(self.origin.x - 1, y), Vec2::new(1, 0))
+    }
+
+    /// The cells of column `x`, top to bottom.
+    pub fn column(&self, x: isize) -> impl Iterator<Item = (Vec2, &T)> {
+        self.ray(Vec2::new(x, self.origin.y - 1), Vec2::new(0, 1))
+    }
+
+    /// The cells seen walking from `from` in steps of `step`, not including `from` itself,
+    /// until the walk leaves the grid.
+    pub fn ray(&self, from: Vec2, step: Vec2) -> impl Iterator<Item = (Vec2, &T)> {
+        assert_ne!(step, Vec2::origin(), "A ray needs a non-zero step");
+        iter::successors(Some(from + step), move |p| Some(*p + step))
+            .map_while(|p| self.get(p).map(|c| (p, c)))
+    }
+
+    /// The orthogonally adjacent cells that are inside the grid.
+    pub fn neighbours4(&self, p: Vec2) -> impl Iterator<Item = (Vec2, &T)> {
+        p.neighbours4().filter_map(|n| self.get(n).map(|c| (n, c)))
+    }
+
+    /// The adjacent cells, including diagonals, that are inside the grid.
+    pub fn neighbours8(&self, p: Vec2) -> impl Iterator<Item = (Vec2, &T)> {
+        p.neighbours8().filter_map(|n| self.get(n).map(|c| (n, c)))
+    }
+
+    /// Displays the grid one row per line, drawing each cell with `f`.
+    pub fn display_with<F>(&self, f: F) -> GridDisplay<'_, T, F>
+    where
+        F: Fn(&T) -> char,
+    {
+        GridDisplay { grid: self, f }
+    }
+}
+
+impl<T> Index<Vec2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Vec2) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{} is outside the grid", p))
+    }
+}
+
+impl<T> IndexMut<Vec2> for Grid<T> {
+    fn index_mut(&mut self, p: Vec2) -> &mut T {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{} is outside the grid", p))
+    }
+}
+
+pub struct GridDisplay<'a, T, F> {
+    grid: &'a Grid<T>,
+    f: F,
+}
+
+impl<T, F> fmt::Display for GridDisplay<'_, T, F>
+where
+    F: Fn(&T) -> char,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.grid.rows() {
+            for cell in row {
+                f.write_char((self.f)(cell))?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TEST_DATA: &str = "123
+456";
+
+    fn digit(c: char) -> Option<u32> {
+        c.to_digit(10)
+    }
+
+    fn digits() -> Grid<u32> {
+        Grid::parse(TEST_DATA, digit).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let g = digits();
+        assert_eq!(g.width(), 3);
+        assert_eq!(g.height(), 2);
+        assert_eq!(g[Vec2::new(0, 0)], 1);
+        assert_eq!(g[Vec2::new(2, 0)], 3);
+        assert_eq!(g[Vec2::new(0, 1)], 4);
+        assert_eq!(g.get(Vec2::new(3, 0)), None);
+        assert_eq!(g.get(Vec2::new(0, -1)), None);
+
+        let e = Grid::parse("12\n345", digit).unwrap_err();
+        assert_eq!(e.line, 2);
+        let e = Grid::parse("12\n3x", digit).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 2));
+        assert_eq!(e.to_string(), "line 2, column 2: unexpected \"x\"");
+    }
+
+    #[test]
+    fn test_origin() {
+        let mut g = Grid::from_corners(Vec2::new(494, 0), Vec2::new(503, 9), '.');
+        assert_eq!(g.size(), Vec2::new(10, 10));
+        assert!(g.in_bounds(Vec2::new(494, 0)));
+        assert!(!g.in_bounds(Vec2::new(493, 0)));
+        assert!(!g.in_bounds(Vec2::new(504, 9)));
+
+        g[Vec2::new(500, 9)] = '#';
+        *g.get_mut(Vec2::new(503, 9)).unwrap() = '#';
+        assert_eq!(g.get(Vec2::new(500, 9)), Some(&'#'));
+        assert_eq!(g.positions().next(), Some(Vec2::new(494, 0)));
+        assert_eq!(g.positions().last(), Some(Vec2::new(503, 9)));
+        assert_eq!(g.iter().filter(|(_, c)| **c == '#').count(), 2);
+    }
+
+    #[test]
+    fn test_lines() {
+        let g = digits();
+        let row = g.row(1).map(|(_, c)| *c).collect::<Vec<_>>();
+        assert_eq!(row, vec![4, 5, 6]);
+        let column = g.column(2).map(|(_, c)| *c).collect::<Vec<_>>();
+        assert_eq!(column, vec![3, 6]);
+
+        let ray = g.ray(Vec2::new(2, 1), Vec2::new(-1, 0)).collect::<Vec<_>>();
+        assert_eq!(ray, vec![(Vec2::new(1, 1), &5), (Vec2::new(0, 1), &4)]);
+        assert_eq!(g.ray(Vec2::new(2, 1), Vec2::new(1, 0)).count(), 0);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let g = digits();
+        assert_eq!(g.neighbours4(Vec2::new(0, 0)).count(), 2);
+        assert_eq!(g.neighbours4(Vec2::new(1, 0)).count(), 3);
+        assert_eq!(g.neighbours8(Vec2::new(1, 0)).count(), 5);
+        let sum: u32 = g.neighbours8(Vec2::new(0, 0)).map(|(_, c)| *c).sum();
+        assert_eq!(sum, 2 + 4 + 5);
+    }
+
+    #[test]
+    fn test_display() {
+        let g = digits();
+        let s = g
+            .display_with(|c| if c % 2 == 0 { '#' } else { '.' })
+            .to_string();
+        assert_eq!(s, ".#.\n#.#\n");
+    }
+}
@@ -0,0 +1,398 @@
+use anyhow::anyhow;
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    render::{Colour, Frame, Part},
+    svg::Svg,
+    Grid, Solution, Vec2,
+};
+
+/// A colour, as red, green and blue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    /// The colour as SVG and HTML write it, such as "#ff8000".
+    pub fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+impl From<Colour> for Rgb {
+    /// The colour a typical dark terminal shows.
+    fn from(colour: Colour) -> Self {
+        match colour {
+            Colour::Grey => Rgb(102, 102, 102),
+            Colour::Red => Rgb(205, 49, 49),
+            Colour::Green => Rgb(13, 188, 121),
+            Colour::Yellow => Rgb(229, 229, 16),
+            Colour::Blue => Rgb(36, 114, 200),
+            Colour::Magenta => Rgb(188, 63, 188),
+            Colour::Cyan => Rgb(17, 168, 205),
+            Colour::White => Rgb(229, 229, 229),
+        }
+    }
+}
+
+/// A picture made of pixels, at least one wide and high.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        let (width, height) = (width.max(1), height.max(1));
+        Image {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// Draws each cell of `grid` as a pixel, in the colour `palette` gives it.
+    pub fn from_grid<T, F>(grid: &Grid<T>, palette: F) -> Self
+    where
+        F: Fn(&T) -> Rgb,
+    {
+        let mut image = Image::new(grid.width(), grid.height(), Rgb::BLACK);
+        for (p, cell) in grid.iter() {
+            let p = p - grid.origin();
+            image.set(p.x as usize, p.y as usize, palette(cell));
+        }
+        image
+    }
+
+    /// Draws each point as a pixel in its colour, in a picture just big enough for all of them,
+    /// with `background` everywhere else. Later points are drawn over earlier ones.
+    pub fn from_points<I>(points: I, background: Rgb) -> Self
+    where
+        I: IntoIterator<Item = (Vec2, Rgb)>,
+    {
+        let points = points.into_iter().collect::<Vec<_>>();
+        let Some(first) = points.first() else {
+            return Image::new(1, 1, background);
+        };
+        let (mut min, mut max) = (first.0, first.0);
+        for (p, _) in &points {
+            min = Vec2::new(min.x.min(p.x), min.y.min(p.y));
+            max = Vec2::new(max.x.max(p.x), max.y.max(p.y));
+        }
+        let size = max - min + Vec2::new(1, 1);
+        let mut image = Image::new(size.x as usize, size.y as usize, background);
+        for (p, rgb) in points {
+            let p = p - min;
+            image.set(p.x as usize, p.y as usize, rgb);
+        }
+        image
+    }
+
+    /// Draws the whole of `frame`, uncropped, a pixel per glyph: in the glyph's colour, white
+    /// if it has none, or `background` if it is blank.
+    pub fn from_frame(frame: &impl Frame, background: Rgb) -> Self {
+        let (top_left, bottom_right) = frame.bounds();
+        let size = bottom_right - top_left + Vec2::new(1, 1);
+        let mut image = Image::new(size.x.max(0) as usize, size.y.max(0) as usize, background);
+        for y in 0..size.y {
+            for x in 0..size.x {
+                let glyph = frame.glyph(top_left + Vec2::new(x, y));
+                let rgb = match glyph.colour {
+                    Some(colour) => colour.into(),
+                    None if glyph.ch == ' ' => background,
+                    None => Rgb::WHITE,
+                };
+                image.set(x as usize, y as usize, rgb);
+            }
+        }
+        image
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    /// Colours the pixel at (`x`, `y`), if the picture is that big.
+    pub fn set(&mut self, x: usize, y: usize, rgb: Rgb) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = rgb;
+        }
+    }
+
+    /// The picture with each pixel blown up into a `scale` by `scale` square.
+    pub fn scaled(&self, scale: usize) -> Image {
+        let scale = scale.max(1);
+        let mut image = Image::new(self.width * scale, self.height * scale, Rgb::BLACK);
+        for y in 0..image.height {
+            for x in 0..image.width {
+                image.pixels[y * image.width + x] = self.pixels[y / scale * self.width + x / scale];
+            }
+        }
+        image
+    }
+
+    /// The picture as a binary PPM file.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut out = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        for p in &self.pixels {
+            out.extend([p.0, p.1, p.2]);
+        }
+        out
+    }
+
+    /// The picture as a PNG file.
+    pub fn to_png(&self) -> io::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        let mut encoder = png::Encoder::new(&mut out, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let data = self
+            .pixels
+            .iter()
+            .flat_map(|p| [p.0, p.1, p.2])
+            .collect::<Vec<_>>();
+        encoder.write_header()?.write_image_data(&data)?;
+        Ok(out)
+    }
+}
+
+/// The kinds of picture file there are, told apart by extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    Png,
+    Svg,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> anyhow::Result<Self> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("ppm") => Ok(Format::Ppm),
+            Some("png") => Ok(Format::Png),
+            Some("svg") => Ok(Format::Svg),
+            _ => Err(anyhow!(
+                "Can't tell what to write {} as; expected .ppm, .png or .svg",
+                path.display()
+            )),
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Ppm => "ppm",
+            Format::Png => "png",
+            Format::Svg => "svg",
+        }
+    }
+}
+
+/// A picture of a day's working: pixels for grids, or shapes for geometry too big for them.
+pub enum Picture {
+    Raster(Image),
+    Vector(Svg),
+}
+
+impl Picture {
+    /// Writes the picture to `path`, as its extension says. Pixels are blown up by `scale`
+    /// first; shapes can be scaled by whatever shows them.
+    pub fn save(&self, path: &Path, scale: usize) -> anyhow::Result<()> {
+        let bytes = match (self, Format::from_path(path)?) {
+            (Picture::Raster(image), Format::Ppm) => image.scaled(scale).to_ppm(),
+            (Picture::Raster(image), Format::Png) => image.scaled(scale).to_png()?,
+            (Picture::Vector(svg), Format::Svg) => svg.to_string().into_bytes(),
+            (Picture::Raster(_), Format::Svg) => {
+                return Err(anyhow!(
+                    "This is a grid of pixels, so save it as .png or .ppm"
+                ))
+            }
+            (Picture::Vector(_), _) => {
+                return Err(anyhow!("This is made of shapes, so save it as .svg"))
+            }
+        };
+        fs::write(path, bytes).map_err(|e| anyhow!("Could not write {}: {}", path.display(), e))
+    }
+}
+
+/// A day that can be drawn as a picture.
+pub trait Draw: Solution {
+    /// Works through `part` as the solution does, and draws how it ends up.
+    fn draw(model: &Self::Model, params: &Self::Params, part: Part) -> anyhow::Result<Picture>;
+}
+
+/// Writes frames of an animation as numbered picture files in a directory: frame-00001.png,
+/// frame-00002.png and so on.
+pub struct FrameSequence {
+    dir: PathBuf,
+    format: Format,
+    scale: usize,
+    written: usize,
+}
+
+impl FrameSequence {
+    /// Starts a sequence in `dir`, creating it if need be, with each pixel blown up by `scale`.
+    pub fn new(dir: &Path, format: Format, scale: usize) -> io::Result<Self> {
+        if format == Format::Svg {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "frames are pixels, so can only be written as .png or .ppm",
+            ));
+        }
+        fs::create_dir_all(dir)?;
+        Ok(FrameSequence {
+            dir: dir.to_path_buf(),
+            format,
+            scale,
+            written: 0,
+        })
+    }
+
+    /// Writes the next frame, returning where it went.
+    pub fn write(&mut self, frame: &impl Frame) -> io::Result<PathBuf> {
+        self.written += 1;
+        let path = self.dir.join(format!(
+            "frame-{:05}.{}",
+            self.written,
+            self.format.extension()
+        ));
+        let image = Image::from_frame(frame, Rgb::BLACK).scaled(self.scale);
+        let bytes = match self.format {
+            Format::Ppm => image.to_ppm(),
+            _ => image.to_png()?,
+        };
+        fs::File::create(&path)?.write_all(&bytes)?;
+        Ok(path)
+    }
+
+    /// How many frames have been written.
+    pub fn written(&self) -> usize {
+        self.written
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::render::Glyph;
+
+    #[test]
+    fn test_from_grid() -> anyhow::Result<()> {
+        let grid = Grid::parse("#.\n.#\n..", Some)?;
+        let image = Image::from_grid(&grid, |c| if *c == '#' { Rgb::WHITE } else { Rgb::BLACK });
+        assert_eq!((image.width(), image.height()), (2, 3));
+        assert_eq!(image.get(1, 1), Some(Rgb::WHITE));
+        assert_eq!(image.get(1, 2), Some(Rgb::BLACK));
+        assert_eq!(image.get(2, 0), None);
+
+        let scaled = image.scaled(3);
+        assert_eq!((scaled.width(), scaled.height()), (6, 9));
+        assert_eq!(scaled.get(5, 5), Some(Rgb::WHITE));
+        assert_eq!(scaled.get(5, 6), Some(Rgb::BLACK));
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_points() {
+        let red = Rgb(255, 0, 0);
+        let image = Image::from_points(
+            [(Vec2::new(-1, 5), red), (Vec2::new(2, 6), red)],
+            Rgb::BLACK,
+        );
+        assert_eq!((image.width(), image.height()), (4, 2));
+        assert_eq!(image.get(0, 0), Some(red));
+        assert_eq!(image.get(3, 1), Some(red));
+        assert_eq!(image.get(1, 0), Some(Rgb::BLACK));
+
+        let empty = Image::from_points([], Rgb::WHITE);
+        assert_eq!(empty.get(0, 0), Some(Rgb::WHITE));
+    }
+
+    #[test]
+    fn test_ppm() {
+        let mut image = Image::new(2, 1, Rgb::BLACK);
+        image.set(1, 0, Rgb(1, 2, 3));
+        assert_eq!(image.to_ppm(), b"P6\n2 1\n255\n\0\0\0\x01\x02\x03");
+    }
+
+    #[test]
+    fn test_png() -> anyhow::Result<()> {
+        let mut image = Image::new(3, 2, Rgb(10, 20, 30));
+        image.set(2, 1, Rgb::WHITE);
+        let png = image.to_png()?;
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+        let mut reader = png::Decoder::new(png.as_slice()).read_info()?;
+        let mut data = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut data)?;
+        assert_eq!((info.width, info.height), (3, 2));
+        assert_eq!(info.color_type, png::ColorType::Rgb);
+        assert_eq!(&data[..3], &[10, 20, 30]);
+        assert_eq!(&data[15..18], &[255, 255, 255]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(
+            Format::from_path(Path::new("out/cave.png")).unwrap(),
+            Format::Png
+        );
+        assert_eq!(
+            Format::from_path(Path::new("rope.svg")).unwrap(),
+            Format::Svg
+        );
+        assert!(Format::from_path(Path::new("cave.jpg")).is_err());
+        assert!(Format::from_path(Path::new("cave")).is_err());
+    }
+
+    struct Dots;
+
+    impl Frame for Dots {
+        fn bounds(&self) -> (Vec2, Vec2) {
+            (Vec2::new(10, 10), Vec2::new(12, 10))
+        }
+
+        fn glyph(&self, p: Vec2) -> Glyph {
+            match p.x {
+                10 => Glyph::coloured('o', Colour::Red),
+                11 => Glyph::BLANK,
+                _ => Glyph::plain('#'),
+            }
+        }
+    }
+
+    #[test]
+    fn test_frames() -> anyhow::Result<()> {
+        let image = Image::from_frame(&Dots, Rgb::BLACK);
+        assert_eq!((image.width(), image.height()), (3, 1));
+        assert_eq!(image.get(0, 0), Some(Colour::Red.into()));
+        assert_eq!(image.get(1, 0), Some(Rgb::BLACK));
+        assert_eq!(image.get(2, 0), Some(Rgb::WHITE));
+
+        let dir = std::env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+        let mut frames = FrameSequence::new(&dir, Format::Ppm, 2)?;
+        frames.write(&Dots)?;
+        let last = frames.write(&Dots)?;
+        assert_eq!(last, dir.join("frame-00002.ppm"));
+        assert_eq!(fs::read(&last)?, image.scaled(2).to_ppm());
+        fs::remove_dir_all(&dir)?;
+
+        assert!(FrameSequence::new(&dir, Format::Svg, 1).is_err());
+        Ok(())
+    }
+}
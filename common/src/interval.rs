@@ -0,0 +1,404 @@
+use std::fmt;
+
+/// Integers that can bound an interval.
+pub trait Bound: Copy + Ord + fmt::Debug {
+    /// The next integer up, if there is one.
+    fn succ(self) -> Option<Self>;
+    /// The next integer down, if there is one.
+    fn pred(self) -> Option<Self>;
+    /// How many integers there are from `lower` to `upper` inclusive, up to `u64::MAX`.
+    fn count(lower: Self, upper: Self) -> u64;
+}
+
+macro_rules! bound {
+    ($($t:ty),*) => {
+        $(
+            impl Bound for $t {
+                fn succ(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn pred(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn count(lower: Self, upper: Self) -> u64 {
+                    let count = upper as i128 - lower as i128 + 1;
+                    u64::try_from(count.max(0)).unwrap_or(u64::MAX)
+                }
+            }
+        )*
+    };
+}
+
+bound!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// The integers from `lower` to `upper`, including both. Never empty.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval<T> {
+    lower: T,
+    upper: T,
+}
+
+impl<T: Bound> Interval<T> {
+    /// The integers from `a` to `b`, whichever way round they are.
+    pub fn new(a: T, b: T) -> Self {
+        Interval {
+            lower: a.min(b),
+            upper: a.max(b),
+        }
+    }
+
+    pub fn single(x: T) -> Self {
+        Interval { lower: x, upper: x }
+    }
+
+    pub fn lower(&self) -> T {
+        self.lower
+    }
+
+    pub fn upper(&self) -> T {
+        self.upper
+    }
+
+    /// How many integers the interval holds.
+    pub fn size(&self) -> u64 {
+        T::count(self.lower, self.upper)
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        self.lower <= x && x <= self.upper
+    }
+
+    /// Whether every integer in `other` is in this one too.
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        self.lower <= other.lower && other.upper <= self.upper
+    }
+
+    /// Whether the two share any integers.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.lower <= other.upper && other.lower <= self.upper
+    }
+
+    /// Whether the two overlap or are next to each other, so make one interval together.
+    pub fn touches(&self, other: &Self) -> bool {
+        let (first, second) = if self.lower <= other.lower {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        first.upper.succ().is_none_or(|next| second.lower <= next)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.overlaps(other).then(|| Interval {
+            lower: self.lower.max(other.lower),
+            upper: self.upper.min(other.upper),
+        })
+    }
+
+    /// The two as one interval, if they touch.
+    pub fn merge(&self, other: &Self) -> Option<Self> {
+        self.touches(other).then(|| Interval {
+            lower: self.lower.min(other.lower),
+            upper: self.upper.max(other.upper),
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.lower, self.upper)
+    }
+}
+
+/// A set of integers, kept as the fewest intervals that cover it: in order, and with no two
+/// overlapping or next to each other.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+/// Sorts `intervals` and merges any that touch.
+fn normalise<T: Bound>(mut intervals: Vec<Interval<T>>) -> Vec<Interval<T>> {
+    intervals.sort();
+    let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+    for interval in intervals {
+        match merged.last_mut() {
+            Some(last) if last.touches(&interval) => last.upper = last.upper.max(interval.upper),
+            _ => merged.push(interval),
+        }
+    }
+    merged
+}
+
+impl<T: Bound> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The intervals making up the set, lowest first.
+    pub fn iter(&self) -> std::slice::Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+
+    /// How many integers the set holds.
+    pub fn size(&self) -> u64 {
+        self.intervals.iter().map(|i| i.size()).sum()
+    }
+
+    /// The interval in the set holding `x`, if any.
+    fn find(&self, x: T) -> Option<&Interval<T>> {
+        let after = self.intervals.partition_point(|i| i.upper < x);
+        self.intervals.get(after).filter(|i| i.lower <= x)
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        self.find(x).is_some()
+    }
+
+    /// Whether every integer in `interval` is in the set.
+    pub fn contains_interval(&self, interval: &Interval<T>) -> bool {
+        self.find(interval.lower)
+            .is_some_and(|i| i.contains_interval(interval))
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        // Everything before `start` ends too soon to touch it, and everything from `end` on
+        // starts too late
+        let start = self
+            .intervals
+            .partition_point(|i| i.upper < interval.lower && !i.touches(&interval));
+        let end = self
+            .intervals
+            .partition_point(|i| i.lower <= interval.upper || i.touches(&interval));
+        let merged = self.intervals[start..end]
+            .iter()
+            .fold(interval, |acc, i| acc.merge(i).unwrap_or(acc));
+        self.intervals.splice(start..end, [merged]);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let both = self.intervals.iter().chain(&other.intervals).copied();
+        IntervalSet {
+            intervals: normalise(both.collect()),
+        }
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.intervals.iter(), other.intervals.iter());
+        let (mut x, mut y) = (a.next(), b.next());
+        let mut intervals = Vec::new();
+        while let (Some(i), Some(j)) = (x, y) {
+            intervals.extend(i.intersection(j));
+            // Whichever ends first can't overlap anything further on in the other
+            if i.upper < j.upper {
+                x = a.next();
+            } else {
+                y = b.next();
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    /// The integers in this set but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let mut removing = other.intervals.iter().peekable();
+        for interval in &self.intervals {
+            let mut rest = Some(*interval);
+            while let (Some(r), Some(cut)) = (rest, removing.peek()) {
+                if cut.upper < r.lower {
+                    removing.next();
+                    continue;
+                }
+                if cut.lower > r.upper {
+                    break;
+                }
+                if cut.lower > r.lower {
+                    // Safe, as there is an integer below cut.lower
+                    intervals.push(Interval::new(r.lower, cut.lower.pred().unwrap()));
+                }
+                rest = cut
+                    .upper
+                    .succ()
+                    .filter(|next| *next <= r.upper)
+                    .map(|next| Interval::new(next, r.upper));
+                if cut.upper <= r.upper {
+                    removing.next();
+                }
+            }
+            intervals.extend(rest);
+        }
+        IntervalSet { intervals }
+    }
+
+    /// The runs of integers missing between the lowest and highest in the set.
+    pub fn gaps(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals.windows(2).map(|pair| {
+            // Safe, as intervals next to each other would have been merged
+            Interval::new(pair[0].upper.succ().unwrap(), pair[1].lower.pred().unwrap())
+        })
+    }
+}
+
+impl<T: Bound> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        IntervalSet {
+            intervals: normalise(iter.into_iter().collect()),
+        }
+    }
+}
+
+impl<T: Bound> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        IntervalSet {
+            intervals: vec![interval],
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Interval<T>;
+    type IntoIter = std::slice::Iter<'a, Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals
+            .iter()
+            .map(|(a, b)| Interval::new(*a, *b))
+            .collect()
+    }
+
+    fn pairs(set: &IntervalSet<i32>) -> Vec<(i32, i32)> {
+        set.iter().map(|i| (i.lower(), i.upper())).collect()
+    }
+
+    /// Every integer in the set, to check against.
+    fn members(set: &IntervalSet<i32>) -> BTreeSet<i32> {
+        set.iter().flat_map(|i| i.lower()..=i.upper()).collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let i = Interval::new(5, 2);
+        assert_eq!((i.lower(), i.upper(), i.size()), (2, 5, 4));
+        assert_eq!(i.to_string(), "2-5");
+        assert!(i.contains(2) && i.contains(5) && !i.contains(6));
+        assert!(i.contains_interval(&Interval::new(3, 5)));
+        assert!(!i.contains_interval(&Interval::new(3, 6)));
+
+        assert!(i.overlaps(&Interval::single(5)));
+        assert!(!i.overlaps(&Interval::new(6, 8)));
+        assert!(i.touches(&Interval::new(6, 8)));
+        assert!(!i.touches(&Interval::new(7, 8)));
+        assert_eq!(
+            i.intersection(&Interval::new(4, 9)),
+            Some(Interval::new(4, 5))
+        );
+        assert_eq!(i.intersection(&Interval::new(6, 9)), None);
+        assert_eq!(i.merge(&Interval::new(6, 9)), Some(Interval::new(2, 9)));
+        assert_eq!(Interval::new(6, 9).merge(&i), Some(Interval::new(2, 9)));
+        assert_eq!(i.merge(&Interval::new(7, 9)), None);
+    }
+
+    #[test]
+    fn test_extremes() {
+        let all = Interval::new(u8::MIN, u8::MAX);
+        assert_eq!(all.size(), 256);
+        assert!(all.touches(&Interval::single(u8::MAX)));
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).size(), u64::MAX);
+
+        let set = IntervalSet::from(all).difference(&IntervalSet::from(Interval::new(1, 254)));
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            [Interval::single(0), Interval::single(255)]
+        );
+    }
+
+    #[test]
+    fn test_normalise() {
+        let s = set(&[(10, 12), (0, 5), (3, 4), (6, 7), (14, 14)]);
+        assert_eq!(pairs(&s), [(0, 7), (10, 12), (14, 14)]);
+        assert_eq!(s.size(), 12);
+        assert_eq!(
+            s.gaps().collect::<Vec<_>>(),
+            [Interval::new(8, 9), Interval::single(13)]
+        );
+        assert!(s.contains(7) && !s.contains(8) && s.contains(14));
+        assert!(s.contains_interval(&Interval::new(1, 6)));
+        assert!(!s.contains_interval(&Interval::new(6, 10)));
+        assert!(IntervalSet::<i32>::new().is_empty());
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut s = set(&[(0, 2), (6, 8), (12, 14)]);
+        s.insert(Interval::new(4, 4));
+        assert_eq!(pairs(&s), [(0, 2), (4, 4), (6, 8), (12, 14)]);
+        s.insert(Interval::new(3, 3));
+        assert_eq!(pairs(&s), [(0, 4), (6, 8), (12, 14)]);
+        s.insert(Interval::new(7, 11));
+        assert_eq!(pairs(&s), [(0, 4), (6, 14)]);
+        s.insert(Interval::new(-5, 20));
+        assert_eq!(pairs(&s), [(-5, 20)]);
+    }
+
+    #[test]
+    fn test_operations() {
+        let a = set(&[(0, 5), (10, 15), (20, 25)]);
+        let b = set(&[(3, 12), (14, 21), (30, 31)]);
+        assert_eq!(pairs(&a.union(&b)), [(0, 25), (30, 31)]);
+        assert_eq!(
+            pairs(&a.intersection(&b)),
+            [(3, 5), (10, 12), (14, 15), (20, 21)]
+        );
+        assert_eq!(pairs(&a.difference(&b)), [(0, 2), (13, 13), (22, 25)]);
+        assert_eq!(pairs(&b.difference(&a)), [(6, 9), (16, 19), (30, 31)]);
+    }
+
+    #[test]
+    fn test_against_sets() {
+        // Every pair from a spread of small sets, checked against the integers they hold
+        let sets = [
+            set(&[]),
+            set(&[(0, 0)]),
+            set(&[(0, 3), (5, 9)]),
+            set(&[(2, 6)]),
+            set(&[(1, 1), (3, 3), (5, 5), (7, 7)]),
+            set(&[(-2, 4), (8, 12)]),
+        ];
+        for a in &sets {
+            for b in &sets {
+                let (x, y) = (members(a), members(b));
+                assert_eq!(members(&a.union(b)), &x | &y);
+                assert_eq!(members(&a.intersection(b)), &x & &y);
+                assert_eq!(members(&a.difference(b)), &x - &y);
+                for s in [a.union(b), a.intersection(b), a.difference(b)] {
+                    assert_eq!(s, s.iter().copied().collect());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+pub mod answers;
+pub mod example;
+pub mod grid;
+pub mod image;
+pub mod interval;
+pub mod parse;
+pub mod reference;
+pub mod render;
+pub mod search;
+pub mod solution;
+pub mod svg;
+pub mod topk;
+pub mod vec2;
+
+pub use answers::{Answers, Details};
+pub use example::Example;
+pub use grid::Grid;
+pub use interval::{Interval, IntervalSet};
+pub use parse::{ParseError, ParseErrorKind};
+pub use solution::{NoParams, Solution, Timings};
+pub use topk::TopK;
+pub use vec2::{Direction, Vec2};
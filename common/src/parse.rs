@@ -0,0 +1,187 @@
+use core::fmt;
+use std::str::FromStr;
+
+/// What was wrong with a piece of input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// Not a number, or out of range for its type
+    InvalidNumber,
+    /// A word or character that isn't valid here
+    Unexpected,
+    /// Something required is missing; says what should have been there
+    Expected(&'static str),
+    /// Well-formed, but breaks some other rule of the puzzle
+    Invalid(String),
+}
+
+/// An error in puzzle input, with where it was found.
+///
+/// Lines and columns count from 1, and 0 means "not known". Parsers that only see one line leave
+/// the line number for their caller to fill in with `at_line`, and parsers that only see part of
+/// a line can have their columns moved into place with `within`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub kind: ParseErrorKind,
+}
+
+/// The column (counting from 1) at which `part` starts, if it is a slice of `line`.
+fn column_of(line: &str, part: &str) -> Option<usize> {
+    let start = line.as_ptr() as usize;
+    let offset = (part.as_ptr() as usize).checked_sub(start)?;
+    if offset + part.len() <= line.len() && line.is_char_boundary(offset) {
+        Some(line[..offset].chars().count() + 1)
+    } else {
+        None
+    }
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, text: &str) -> Self {
+        ParseError {
+            line: 0,
+            column: 0,
+            text: text.to_string(),
+            kind,
+        }
+    }
+
+    /// An error about `text`, which should be a slice of `line` so that the column can be found.
+    pub fn in_line(kind: ParseErrorKind, line: &str, text: &str) -> Self {
+        ParseError {
+            column: column_of(line, text).unwrap_or(0),
+            ..ParseError::new(kind, text)
+        }
+    }
+
+    /// Sets the line number, unless it is already known.
+    pub fn at_line(mut self, line: usize) -> Self {
+        if self.line == 0 {
+            self.line = line;
+        }
+        self
+    }
+
+    /// Moves the line number down by `lines`, for errors numbered from the start of a
+    /// multi-line record rather than from the start of the input.
+    pub fn offset_lines(mut self, lines: usize) -> Self {
+        if self.line != 0 {
+            self.line += lines;
+        }
+        self
+    }
+
+    /// Moves the column along to account for `inner` starting part way through `outer`.
+    pub fn within(mut self, outer: &str, inner: &str) -> Self {
+        if let Some(start) = column_of(outer, inner) {
+            self.column = if self.column == 0 {
+                start
+            } else {
+                self.column + start - 1
+            };
+        }
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line != 0 {
+            f.write_fmt(format_args!("line {}", self.line))?;
+            if self.column != 0 {
+                f.write_fmt(format_args!(", column {}", self.column))?;
+            }
+            f.write_str(": ")?;
+        }
+        match &self.kind {
+            ParseErrorKind::InvalidNumber => f.write_str("invalid number")?,
+            ParseErrorKind::Unexpected => f.write_str("unexpected")?,
+            ParseErrorKind::Expected(what) => {
+                f.write_fmt(format_args!("expected {}, found", what))?
+            }
+            ParseErrorKind::Invalid(why) => f.write_str(why)?,
+        }
+        if self.text.is_empty() {
+            f.write_str(" nothing")
+        } else {
+            f.write_fmt(format_args!(" {:?}", self.text))
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `text` as a number, reporting its position in `line` if it isn't one.
+pub fn parse_number<T: FromStr>(line: &str, text: &str) -> Result<T, ParseError> {
+    text.parse()
+        .map_err(|_| ParseError::in_line(ParseErrorKind::InvalidNumber, line, text))
+}
+
+/// Parses each of `lines` with `f`, tagging any error with its line number.
+pub fn parse_lines<I, S, T, F>(lines: I, mut f: F) -> Result<Vec<T>, ParseError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+    F: FnMut(&str) -> Result<T, ParseError>,
+{
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| f(line.as_ref()).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_number() {
+        let line = "move 1 from x to 3";
+        assert_eq!(parse_number::<u32>(line, &line[5..6]), Ok(1));
+
+        let e = parse_number::<u32>(line, &line[12..13]).unwrap_err();
+        assert_eq!(e.kind, ParseErrorKind::InvalidNumber);
+        assert_eq!(e.column, 13);
+        assert_eq!(e.text, "x");
+        assert_eq!(e.to_string(), "invalid number \"x\"");
+        assert_eq!(
+            e.at_line(4).to_string(),
+            "line 4, column 13: invalid number \"x\""
+        );
+    }
+
+    #[test]
+    fn test_positions() {
+        let line = "2-4,6-x";
+        let (_, second) = line.split_once(',').unwrap();
+        let (_, upper) = second.split_once('-').unwrap();
+
+        // Parsed as part of `second` only, then moved into place
+        let e = parse_number::<u32>(second, upper).unwrap_err();
+        assert_eq!(e.column, 3);
+        assert_eq!(e.within(line, second).column, 7);
+
+        let e = ParseError::new(ParseErrorKind::Expected("','"), "").within(line, second);
+        assert_eq!(e.column, 5);
+        assert_eq!(e.to_string(), "expected ',', found nothing");
+
+        let e = ParseError::new(ParseErrorKind::Unexpected, "?")
+            .at_line(2)
+            .offset_lines(10)
+            .at_line(1);
+        assert_eq!(e.line, 12);
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let parsed = parse_lines("1\n2\n3".lines(), |l| parse_number::<u32>(l, l));
+        assert_eq!(parsed, Ok(vec![1, 2, 3]));
+
+        let e = parse_lines("1\n2\nthree".lines(), |l| parse_number::<u32>(l, l)).unwrap_err();
+        assert_eq!((e.line, e.column), (3, 1));
+        assert_eq!(e.to_string(), "line 3, column 1: invalid number \"three\"");
+    }
+}
@@ -0,0 +1,92 @@
+//! Checking a day against the slow but obviously correct solver in its `generate` module.
+
+use crate::{Answers, Solution};
+
+/// Solves `input` with `S` and checks both parts match `expected`, the reference solver's
+/// answers, panicking with the input if not.
+pub fn check_against_reference<S: Solution>(input: &str, expected: &Answers) {
+    let answers = S::solve(input).unwrap_or_else(|e| panic!("{:#}, solving:\n{}", e, input));
+    assert_eq!(
+        (answers.part1.as_str(), answers.part2.as_str()),
+        (expected.part1.as_str(), expected.part2.as_str()),
+        "solving:\n{}",
+        input
+    );
+}
+
+/// Defines a property test that a day answers the inputs from its `generate` module's
+/// `input(rng, size)` as its `reference` does, for sizes in the given range. Expects both to be
+/// in scope, and `proptest` and `rand` among the crate's dependencies.
+///
+/// ```ignore
+/// common::test_against_reference!(crate::Day2, 0..100usize);
+/// common::test_against_reference!(crate::Day11, 0..6usize, cases = 32);
+/// ```
+#[macro_export]
+macro_rules! test_against_reference {
+    ($day:ty, $sizes:expr) => {
+        proptest::proptest! {
+            #[test]
+            fn test_matches_reference(seed: u64, size in $sizes) {
+                $crate::test_against_reference!(@check $day, seed, size);
+            }
+        }
+    };
+    ($day:ty, $sizes:expr, cases = $cases:expr) => {
+        proptest::proptest! {
+            #![proptest_config(proptest::prelude::ProptestConfig::with_cases($cases))]
+
+            #[test]
+            fn test_matches_reference(seed: u64, size in $sizes) {
+                $crate::test_against_reference!(@check $day, seed, size);
+            }
+        }
+    };
+    (@check $day:ty, $seed:expr, $size:expr) => {{
+        use rand::SeedableRng;
+        let input = input(&mut rand::rngs::StdRng::seed_from_u64($seed), $size);
+        $crate::reference::check_against_reference::<$day>(&input, &reference(&input));
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::NoParams;
+
+    /// Counts the lines, getting it wrong for more than two.
+    struct Lines;
+
+    impl Solution for Lines {
+        type Model = usize;
+        type Params = NoParams;
+        type Part1 = usize;
+        type Part2 = usize;
+
+        fn parse_lines<I>(lines: I) -> anyhow::Result<usize>
+        where
+            I: Iterator<Item = String>,
+        {
+            Ok(lines.count().min(2))
+        }
+
+        fn part1(lines: &usize, _: &NoParams) -> anyhow::Result<usize> {
+            Ok(*lines)
+        }
+
+        fn part2(lines: &usize, _: &NoParams) -> anyhow::Result<usize> {
+            Ok(*lines)
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        check_against_reference::<Lines>("a\nb", &Answers::new(2, 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "solving:\na\nb\nc")]
+    fn test_mismatch() {
+        check_against_reference::<Lines>("a\nb\nc", &Answers::new(3, 3));
+    }
+}
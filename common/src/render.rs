@@ -0,0 +1,392 @@
+use anyhow::anyhow;
+use std::{
+    io::{self, Write},
+    str::FromStr,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{image::FrameSequence, Solution, Vec2};
+
+/// Moves the cursor to the top left, so each frame draws over the last without flickering.
+const HOME: &str = "\x1b[H";
+/// Clears the rest of the line, or with `CLEAR_BELOW` the rest of the screen.
+const CLEAR_LINE: &str = "\x1b[K";
+const CLEAR_BELOW: &str = "\x1b[J";
+const RESET: &str = "\x1b[0m";
+
+/// A terminal text colour.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Colour {
+    Grey,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Colour {
+    /// The ANSI escape sequence that switches to this colour.
+    fn escape(self) -> String {
+        let code = match self {
+            Colour::Grey => 90,
+            Colour::Red => 31,
+            Colour::Green => 32,
+            Colour::Yellow => 33,
+            Colour::Blue => 34,
+            Colour::Magenta => 35,
+            Colour::Cyan => 36,
+            Colour::White => 97,
+        };
+        format!("\x1b[{}m", code)
+    }
+}
+
+/// One character of a frame, in the terminal's own colour unless it has one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Glyph {
+    pub ch: char,
+    pub colour: Option<Colour>,
+}
+
+impl Glyph {
+    pub const BLANK: Glyph = Glyph::plain(' ');
+
+    pub const fn plain(ch: char) -> Self {
+        Glyph { ch, colour: None }
+    }
+
+    pub const fn coloured(ch: char, colour: Colour) -> Self {
+        Glyph {
+            ch,
+            colour: Some(colour),
+        }
+    }
+}
+
+/// A picture of a simulation at one moment, with y increasing down the screen.
+pub trait Frame {
+    /// The top-left and bottom-right corners of everything worth drawing, inclusive.
+    fn bounds(&self) -> (Vec2, Vec2);
+
+    /// What to draw at `p`.
+    fn glyph(&self, p: Vec2) -> Glyph;
+
+    /// Where the action is, to keep in view when the frame is too big to show whole. The middle
+    /// of the bounds by default.
+    fn focus(&self) -> Option<Vec2> {
+        None
+    }
+
+    /// A line about the frame to show above it. None by default.
+    fn caption(&self) -> String {
+        String::new()
+    }
+}
+
+/// The camera: how much of a frame fits on screen. Frames too big for it are cropped around
+/// their focus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Viewport {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Viewport {
+    pub fn new(width: usize, height: usize) -> Self {
+        Viewport { width, height }
+    }
+
+    /// The top-left and bottom-right corners of the part of `frame` to show.
+    pub fn crop(&self, frame: &impl Frame) -> (Vec2, Vec2) {
+        let (top_left, bottom_right) = frame.bounds();
+        let focus = frame.focus().unwrap_or_else(|| {
+            Vec2::new(
+                midpoint(top_left.x, bottom_right.x),
+                midpoint(top_left.y, bottom_right.y),
+            )
+        });
+        let x = window(top_left.x, bottom_right.x, focus.x, self.width);
+        let y = window(top_left.y, bottom_right.y, focus.y, self.height);
+        (Vec2::new(x.0, y.0), Vec2::new(x.1, y.1))
+    }
+
+    /// Draws the part of `frame` in view, a line per row, coloured with ANSI escapes if
+    /// `colour` is set.
+    pub fn render(&self, frame: &impl Frame, colour: bool) -> String {
+        let (top_left, bottom_right) = self.crop(frame);
+        let mut out = String::new();
+        for y in top_left.y..=bottom_right.y {
+            let mut current = None;
+            for x in top_left.x..=bottom_right.x {
+                let glyph = frame.glyph(Vec2::new(x, y));
+                if colour && glyph.colour != current {
+                    match glyph.colour {
+                        Some(c) => out.push_str(&c.escape()),
+                        None => out.push_str(RESET),
+                    }
+                    current = glyph.colour;
+                }
+                out.push(glyph.ch);
+            }
+            if current.is_some() {
+                out.push_str(RESET);
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Viewport::new(80, 24)
+    }
+}
+
+impl FromStr for Viewport {
+    type Err = anyhow::Error;
+
+    /// Parses a size of the form "80x24".
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let size = s.split_once('x').and_then(|(w, h)| {
+            let (w, h) = (w.parse().ok()?, h.parse().ok()?);
+            (w > 0 && h > 0).then_some(Viewport::new(w, h))
+        });
+        size.ok_or_else(|| anyhow!("Expected a size such as 80x24, got {:?}", s))
+    }
+}
+
+fn midpoint(min: isize, max: isize) -> isize {
+    min + (max - min) / 2
+}
+
+/// The first and last positions along one axis of a window `size` long: all of `min..=max` if
+/// it fits, otherwise centred on `focus` but kept within `min..=max`.
+fn window(min: isize, max: isize, focus: isize, size: usize) -> (isize, isize) {
+    let size = size as isize;
+    if max - min < size {
+        return (min, max);
+    }
+    let start = (focus - size / 2).clamp(min, max - size + 1);
+    (start, start + size - 1)
+}
+
+/// Shows frames one after another in the terminal, no faster than its frame rate.
+pub struct Player<W: Write> {
+    out: W,
+    viewport: Viewport,
+    colour: bool,
+    frame_time: Option<Duration>,
+    next: Option<Instant>,
+    shown: usize,
+    recording: Option<FrameSequence>,
+}
+
+impl<W: Write> Player<W> {
+    /// A player showing `fps` frames a second, or as fast as it can if `fps` is 0. The caption
+    /// takes a line of the viewport.
+    pub fn new(out: W, viewport: Viewport, fps: u32, colour: bool) -> Self {
+        Player {
+            out,
+            viewport: Viewport::new(viewport.width, viewport.height.saturating_sub(1).max(1)),
+            colour,
+            frame_time: (fps > 0).then(|| Duration::from_secs(1) / fps),
+            next: None,
+            shown: 0,
+            recording: None,
+        }
+    }
+
+    /// Also writes each frame shown to `frames`, as a picture.
+    pub fn record(self, frames: FrameSequence) -> Self {
+        Player {
+            recording: Some(frames),
+            ..self
+        }
+    }
+
+    /// Waits until the last frame has been up long enough, then draws `frame` over it, and
+    /// records it if recording.
+    pub fn show(&mut self, frame: &impl Frame) -> io::Result<()> {
+        if let Some(next) = self.next {
+            let now = Instant::now();
+            if next > now {
+                thread::sleep(next - now);
+            }
+        }
+
+        let mut screen = String::from(HOME);
+        screen.push_str(frame.caption().lines().next().unwrap_or(""));
+        screen.push_str(CLEAR_LINE);
+        screen.push('\n');
+        for line in self.viewport.render(frame, self.colour).lines() {
+            screen.push_str(line);
+            screen.push_str(CLEAR_LINE);
+            screen.push('\n');
+        }
+        screen.push_str(CLEAR_BELOW);
+        self.out.write_all(screen.as_bytes())?;
+        self.out.flush()?;
+
+        self.next = self.frame_time.map(|t| Instant::now() + t);
+        if let Some(frames) = &mut self.recording {
+            frames.write(frame)?;
+        }
+        self.shown += 1;
+        Ok(())
+    }
+
+    /// How many frames have been shown.
+    pub fn shown(&self) -> usize {
+        self.shown
+    }
+}
+
+/// Which part of a puzzle to watch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+/// A day whose working can be watched a step at a time.
+pub trait Visualise: Solution {
+    /// Works through `part` as the solution does, showing each step on `player`.
+    fn visualise<W: Write>(
+        model: &Self::Model,
+        params: &Self::Params,
+        part: Part,
+        player: &mut Player<W>,
+    ) -> anyhow::Result<()>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A diagonal line across a square, with its focus at one end.
+    struct Diagonal {
+        size: isize,
+        focus: Option<Vec2>,
+    }
+
+    impl Frame for Diagonal {
+        fn bounds(&self) -> (Vec2, Vec2) {
+            (Vec2::origin(), Vec2::new(self.size - 1, self.size - 1))
+        }
+
+        fn glyph(&self, p: Vec2) -> Glyph {
+            if p.x == p.y {
+                Glyph::coloured('\\', Colour::Red)
+            } else {
+                Glyph::plain('.')
+            }
+        }
+
+        fn focus(&self) -> Option<Vec2> {
+            self.focus
+        }
+
+        fn caption(&self) -> String {
+            format!("{} square", self.size)
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let frame = Diagonal {
+            size: 3,
+            focus: None,
+        };
+        let viewport = Viewport::new(10, 10);
+        assert_eq!(viewport.render(&frame, false), "\\..\n.\\.\n..\\\n");
+        assert_eq!(
+            viewport.render(&frame, true).lines().next().unwrap(),
+            "\x1b[31m\\\x1b[0m.."
+        );
+    }
+
+    #[test]
+    fn test_crop() {
+        let viewport = Viewport::new(4, 2);
+        let mut frame = Diagonal {
+            size: 10,
+            focus: None,
+        };
+        assert_eq!(viewport.crop(&frame), (Vec2::new(2, 3), Vec2::new(5, 4)));
+
+        // The focus is kept in view, without showing anything past the bounds
+        frame.focus = Some(Vec2::new(9, 0));
+        assert_eq!(viewport.crop(&frame), (Vec2::new(6, 0), Vec2::new(9, 1)));
+        frame.focus = Some(Vec2::new(5, 5));
+        assert_eq!(viewport.render(&frame, false), ".\\..\n..\\.\n");
+    }
+
+    #[test]
+    fn test_parse_viewport() {
+        assert_eq!(
+            "120x40".parse::<Viewport>().unwrap(),
+            Viewport::new(120, 40)
+        );
+        assert!("120".parse::<Viewport>().is_err());
+        assert!("0x40".parse::<Viewport>().is_err());
+    }
+
+    #[test]
+    fn test_player() -> anyhow::Result<()> {
+        let mut out = Vec::new();
+        let mut player = Player::new(&mut out, Viewport::new(4, 3), 0, false);
+        let frame = Diagonal {
+            size: 3,
+            focus: None,
+        };
+        player.show(&frame)?;
+        player.show(&frame)?;
+        assert_eq!(player.shown(), 2);
+
+        // The caption takes the first line, leaving two for the frame
+        let screen = format!(
+            "{HOME}3 square{CLEAR_LINE}\n\\..{CLEAR_LINE}\n.\\.{CLEAR_LINE}\n{CLEAR_BELOW}"
+        );
+        assert_eq!(String::from_utf8(out)?, screen.repeat(2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_record() -> anyhow::Result<()> {
+        use crate::image::Format;
+
+        let dir = std::env::temp_dir().join(format!("aoc-record-{}", std::process::id()));
+        let frames = FrameSequence::new(&dir, Format::Png, 1)?;
+        let mut player = Player::new(io::sink(), Viewport::default(), 0, false).record(frames);
+        let frame = Diagonal {
+            size: 3,
+            focus: None,
+        };
+        player.show(&frame)?;
+        player.show(&frame)?;
+        assert!(dir.join("frame-00002.png").exists());
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_frame_rate() -> anyhow::Result<()> {
+        let mut player = Player::new(io::sink(), Viewport::default(), 50, false);
+        let frame = Diagonal {
+            size: 3,
+            focus: None,
+        };
+        let start = Instant::now();
+        for _ in 0..4 {
+            player.show(&frame)?;
+        }
+        // Each frame after the first waits out the one before's 20ms
+        assert!(start.elapsed() >= Duration::from_millis(60));
+        Ok(())
+    }
+}
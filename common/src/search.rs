@@ -0,0 +1,371 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// What a search found: the distance to every node it reached from the nearest start, how each
+/// node was first reached, and the goal it stopped at (if any).
+///
+/// To get a full distance map, search with a goal predicate that never matches.
+#[derive(Clone, Debug)]
+pub struct SearchResult<N, C> {
+    distances: HashMap<N, C>,
+    came_from: HashMap<N, N>,
+    goal: Option<N>,
+}
+
+impl<N, C> SearchResult<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Copy,
+{
+    fn new() -> Self {
+        SearchResult {
+            distances: HashMap::new(),
+            came_from: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    pub fn goal_distance(&self) -> Option<C> {
+        self.goal.as_ref().and_then(|g| self.distance(g))
+    }
+
+    pub fn distance(&self, n: &N) -> Option<C> {
+        self.distances.get(n).copied()
+    }
+
+    pub fn distances(&self) -> &HashMap<N, C> {
+        &self.distances
+    }
+
+    /// The route from a start to `n`, including both ends.
+    pub fn path_to(&self, n: &N) -> Option<Vec<N>> {
+        if !self.distances.contains_key(n) {
+            return None;
+        }
+        let mut path = vec![n.clone()];
+        while let Some(previous) = self.came_from.get(path.last().unwrap()) {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// The route from a start to the goal, including both ends.
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.goal.as_ref().and_then(|g| self.path_to(g))
+    }
+}
+
+/// Breadth-first search, where every step costs 1.
+pub fn bfs<N, S, F, I, G>(starts: S, mut neighbours: F, mut is_goal: G) -> SearchResult<N, usize>
+where
+    N: Eq + Hash + Clone,
+    S: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    G: FnMut(&N) -> bool,
+{
+    let mut result = SearchResult::new();
+    let mut to_visit = VecDeque::new();
+
+    for start in starts {
+        if !result.distances.contains_key(&start) {
+            result.distances.insert(start.clone(), 0);
+            to_visit.push_back(start);
+        }
+    }
+
+    while let Some(visiting) = to_visit.pop_front() {
+        if is_goal(&visiting) {
+            result.goal = Some(visiting);
+            break;
+        }
+        let distance = result.distances[&visiting] + 1;
+        for n in neighbours(&visiting) {
+            if !result.distances.contains_key(&n) {
+                result.distances.insert(n.clone(), distance);
+                result.came_from.insert(n.clone(), visiting.clone());
+                to_visit.push_back(n);
+            }
+        }
+    }
+
+    result
+}
+
+/// Breadth-first search a layer at a time, for watching it spread: yields the starts, then every
+/// node first reached one step further out, and so on until nothing new is reached.
+pub fn bfs_layers<N, S, F, I>(starts: S, neighbours: F) -> Layers<N, F>
+where
+    N: Eq + Hash + Clone,
+    S: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let layer = starts
+        .into_iter()
+        .filter(|n| seen.insert(n.clone()))
+        .collect();
+    Layers {
+        seen,
+        layer,
+        neighbours,
+    }
+}
+
+/// The layers of a breadth-first search, from `bfs_layers`.
+pub struct Layers<N, F> {
+    seen: HashSet<N>,
+    layer: Vec<N>,
+    neighbours: F,
+}
+
+impl<N, F, I> Iterator for Layers<N, F>
+where
+    N: Eq + Hash + Clone,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    type Item = Vec<N>;
+
+    fn next(&mut self) -> Option<Vec<N>> {
+        if self.layer.is_empty() {
+            return None;
+        }
+        let mut next = Vec::new();
+        for visiting in &self.layer {
+            for n in (self.neighbours)(visiting) {
+                if self.seen.insert(n.clone()) {
+                    next.push(n);
+                }
+            }
+        }
+        Some(std::mem::replace(&mut self.layer, next))
+    }
+}
+
+/// Dijkstra's algorithm, for non-negative step costs. `neighbours` gives each neighbour with the
+/// cost of stepping to it.
+pub fn dijkstra<N, C, S, F, I, G>(starts: S, neighbours: F, is_goal: G) -> SearchResult<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    S: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    G: FnMut(&N) -> bool,
+{
+    astar(starts, neighbours, |_| C::default(), is_goal)
+}
+
+/// A* search. `heuristic` must never overestimate the remaining cost to a goal, or the distances
+/// found may not be the shortest.
+pub fn astar<N, C, S, F, I, H, G>(
+    starts: S,
+    mut neighbours: F,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> SearchResult<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    S: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: FnMut(&N) -> C,
+    G: FnMut(&N) -> bool,
+{
+    let mut result = SearchResult::new();
+    let mut to_visit = BinaryHeap::new();
+
+    for start in starts {
+        if !result.distances.contains_key(&start) {
+            let priority = heuristic(&start);
+            result.distances.insert(start.clone(), C::default());
+            to_visit.push(Queued {
+                priority,
+                cost: C::default(),
+                node: start,
+            });
+        }
+    }
+
+    while let Some(Queued { cost, node, .. }) = to_visit.pop() {
+        // The node may have been queued again since with a lower cost
+        if cost > result.distances[&node] {
+            continue;
+        }
+        if is_goal(&node) {
+            result.goal = Some(node);
+            break;
+        }
+        for (n, step) in neighbours(&node) {
+            let n_cost = cost + step;
+            if result.distances.get(&n).is_none_or(|c| n_cost < *c) {
+                result.distances.insert(n.clone(), n_cost);
+                result.came_from.insert(n.clone(), node.clone());
+                to_visit.push(Queued {
+                    priority: n_cost + heuristic(&n),
+                    cost: n_cost,
+                    node: n,
+                });
+            }
+        }
+    }
+
+    result
+}
+
+/// A node waiting in the A* queue, ordered so the `BinaryHeap` pops the lowest priority first.
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Grid, Vec2};
+
+    const MAZE: &str = "S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E";
+
+    fn maze() -> Grid<char> {
+        Grid::parse(MAZE, Some).unwrap()
+    }
+
+    fn open_neighbours(grid: &Grid<char>, p: Vec2) -> Vec<Vec2> {
+        grid.neighbours4(p)
+            .filter(|(_, c)| **c != '#')
+            .map(|(n, _)| n)
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = maze();
+        let end = Vec2::new(7, 4);
+        let result = bfs(
+            [Vec2::new(0, 0)],
+            |p| open_neighbours(&grid, *p),
+            |p| *p == end,
+        );
+        assert_eq!(result.goal(), Some(&end));
+        assert_eq!(result.goal_distance(), Some(15));
+
+        let path = result.path().unwrap();
+        assert_eq!(path.len(), 16);
+        assert_eq!(path[0], Vec2::new(0, 0));
+        assert_eq!(path[15], end);
+        assert!(path.windows(2).all(|w| w[0].manhattan(&w[1]) == 1));
+    }
+
+    #[test]
+    fn test_bfs_multi_source() {
+        let grid = maze();
+        let end = Vec2::new(7, 4);
+        let result = bfs(
+            [Vec2::new(0, 0), Vec2::new(7, 0)],
+            |p| open_neighbours(&grid, *p),
+            |p| *p == end,
+        );
+        assert_eq!(result.goal_distance(), Some(4));
+        assert_eq!(result.path().unwrap()[0], Vec2::new(7, 0));
+    }
+
+    #[test]
+    fn test_distance_map() {
+        let grid = maze();
+        let result = bfs([Vec2::new(0, 0)], |p| open_neighbours(&grid, *p), |_| false);
+        assert_eq!(result.goal(), None);
+        assert_eq!(
+            result.distances().len(),
+            grid.iter().filter(|(_, c)| **c != '#').count()
+        );
+        assert_eq!(result.distance(&Vec2::new(2, 0)), Some(2));
+        assert_eq!(result.distance(&Vec2::new(3, 0)), None);
+        assert_eq!(result.path_to(&Vec2::new(3, 0)), None);
+    }
+
+    #[test]
+    fn test_bfs_layers() {
+        let grid = maze();
+        let start = Vec2::new(0, 0);
+        let result = bfs([start], |p| open_neighbours(&grid, *p), |_| false);
+        let layers = bfs_layers([start], |p| open_neighbours(&grid, *p)).collect::<Vec<_>>();
+
+        assert_eq!(layers[0], vec![start]);
+        assert_eq!(
+            layers.iter().map(|l| l.len()).sum::<usize>(),
+            result.distances().len()
+        );
+        for (distance, layer) in layers.iter().enumerate() {
+            assert!(layer.iter().all(|p| result.distance(p) == Some(distance)));
+        }
+    }
+
+    #[test]
+    fn test_weighted() {
+        // Going through a digit costs that much; the long way round costs 1 per step
+        let grid = Grid::parse("S9E\n.9.\n...", Some).unwrap();
+        let cost = |c: char| c.to_digit(10).unwrap_or(1) as usize;
+        let neighbours = |p: &Vec2| {
+            grid.neighbours4(*p)
+                .map(|(n, c)| (n, cost(*c)))
+                .collect::<Vec<_>>()
+        };
+        let end = Vec2::new(2, 0);
+
+        let result = dijkstra([Vec2::new(0, 0)], neighbours, |p| *p == end);
+        assert_eq!(result.goal_distance(), Some(6));
+        assert_eq!(result.path().unwrap().len(), 7);
+
+        let result = astar(
+            [Vec2::new(0, 0)],
+            neighbours,
+            |p| p.manhattan(&end),
+            |p| *p == end,
+        );
+        assert_eq!(result.goal_distance(), Some(6));
+    }
+
+    #[test]
+    fn test_start_is_goal() {
+        let result = bfs([0], |n: &i32| [n + 1], |n| *n == 0);
+        assert_eq!(result.goal_distance(), Some(0));
+        assert_eq!(result.path(), Some(vec![0]));
+    }
+}
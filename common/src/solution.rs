@@ -0,0 +1,179 @@
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{
+    fmt::Display,
+    io::BufRead,
+    time::{Duration, Instant},
+};
+use tracing::{debug, info_span};
+
+use crate::{Answers, Details, Example};
+
+/// How long each stage of solving a day took.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+/// The parameters of a day that has none. Setting any of them is an error.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NoParams {}
+
+/// A day's puzzle: the input is parsed once into a model, and each part is answered from it.
+pub trait Solution {
+    type Model;
+    /// Settings the puzzle text fixes rather than the input, such as how many rounds to play.
+    /// The default is the real puzzle's.
+    type Params: Default + DeserializeOwned;
+    type Part1: Display;
+    type Part2: Display;
+
+    /// The puzzle's examples, to check the solution against. None by default.
+    const EXAMPLES: &'static [Example] = &[];
+
+    /// Parses the input a line at a time, without the line endings.
+    fn parse_lines<I>(lines: I) -> anyhow::Result<Self::Model>
+    where
+        I: Iterator<Item = String>;
+
+    /// Like `parse_lines`, for days whose input only means something given the parameters,
+    /// such as a game's rules. Ignores them by default.
+    fn parse_lines_with<I>(lines: I, _params: &Self::Params) -> anyhow::Result<Self::Model>
+    where
+        I: Iterator<Item = String>,
+    {
+        Self::parse_lines(lines)
+    }
+
+    /// Parses `input` with the real puzzle's parameters.
+    fn parse(input: &str) -> anyhow::Result<Self::Model> {
+        Self::parse_with(input, &Self::Params::default())
+    }
+
+    fn parse_with(input: &str, params: &Self::Params) -> anyhow::Result<Self::Model> {
+        Self::parse_lines_with(input.lines().map(|l| l.to_string()), params)
+    }
+
+    /// Like `parse_reader_with`, with the real puzzle's parameters.
+    fn parse_reader<R: BufRead>(reader: R) -> anyhow::Result<Self::Model> {
+        Self::parse_reader_with(reader, &Self::Params::default())
+    }
+
+    /// Parses lines from `reader` as they are read, so the input is never held all at once
+    /// unless the model itself needs it.
+    fn parse_reader_with<R: BufRead>(
+        reader: R,
+        params: &Self::Params,
+    ) -> anyhow::Result<Self::Model> {
+        let mut error = None;
+        let lines = reader
+            .lines()
+            .map_while(|line| line.map_err(|e| error = Some(e)).ok());
+        let model = Self::parse_lines_with(lines, params);
+        match error {
+            // Parsing stopped short, so the read error is what went wrong
+            Some(e) => Err(e.into()),
+            None => model,
+        }
+    }
+
+    fn part1(model: &Self::Model, params: &Self::Params) -> anyhow::Result<Self::Part1>;
+
+    fn part2(model: &Self::Model, params: &Self::Params) -> anyhow::Result<Self::Part2>;
+
+    /// Extra results worth reporting alongside the answers. None by default.
+    fn details(_model: &Self::Model, _params: &Self::Params) -> anyhow::Result<Details> {
+        Ok(Details::new())
+    }
+
+    /// Parses `input` and answers both parts of the real puzzle.
+    fn solve(input: &str) -> anyhow::Result<Answers> {
+        Self::solve_with(input, &Self::Params::default())
+    }
+
+    /// Parses `input` and answers both parts with `params`.
+    fn solve_with(input: &str, params: &Self::Params) -> anyhow::Result<Answers> {
+        let model = info_span!("parse").in_scope(|| Self::parse_with(input, params))?;
+        let part1 = info_span!("part1").in_scope(|| Self::part1(&model, params))?;
+        let part2 = info_span!("part2").in_scope(|| Self::part2(&model, params))?;
+        Ok(Answers {
+            details: Self::details(&model, params)?,
+            ..Answers::new(part1, part2)
+        })
+    }
+
+    /// Like `solve_with`, but reads the input from `reader` and also times parsing (which
+    /// includes reading) and each part.
+    fn solve_timed<R: BufRead>(
+        reader: R,
+        params: &Self::Params,
+    ) -> anyhow::Result<(Answers, Timings)> {
+        let start = Instant::now();
+        let model = info_span!("parse").in_scope(|| Self::parse_reader_with(reader, params))?;
+        let parsed = Instant::now();
+        let part1 = info_span!("part1").in_scope(|| Self::part1(&model, params))?;
+        let answered1 = Instant::now();
+        let part2 = info_span!("part2").in_scope(|| Self::part2(&model, params))?;
+        let answered2 = Instant::now();
+
+        let timings = Timings {
+            parse: parsed - start,
+            part1: answered1 - parsed,
+            part2: answered2 - answered1,
+        };
+        debug!(?timings, "solved");
+        let answers = Answers {
+            details: Self::details(&model, params)?,
+            ..Answers::new(part1, part2)
+        };
+        Ok((answers, timings))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct CountLines;
+
+    impl Solution for CountLines {
+        type Model = Vec<String>;
+        type Params = NoParams;
+        type Part1 = usize;
+        type Part2 = usize;
+
+        fn parse_lines<I>(lines: I) -> anyhow::Result<Self::Model>
+        where
+            I: Iterator<Item = String>,
+        {
+            Ok(lines.collect())
+        }
+
+        fn part1(lines: &Self::Model, _: &NoParams) -> anyhow::Result<usize> {
+            Ok(lines.len())
+        }
+
+        fn part2(lines: &Self::Model, _: &NoParams) -> anyhow::Result<usize> {
+            Ok(lines.iter().map(|l| l.len()).sum())
+        }
+    }
+
+    #[test]
+    fn test_parse_reader() -> anyhow::Result<()> {
+        let (answers, _) = CountLines::solve_timed("ab\r\ncd\nef\n".as_bytes(), &NoParams {})?;
+        assert_eq!(answers, CountLines::solve("ab\r\ncd\nef")?);
+        assert_eq!((answers.part1.as_str(), answers.part2.as_str()), ("3", "6"));
+
+        // Invalid UTF-8 can't be read as a line
+        assert!(CountLines::parse_reader(&b"ab\n\xff\ncd\n"[..]).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+use std::fmt::{self, Write};
+
+use crate::{image::Rgb, Vec2};
+
+/// How to paint a shape. Line widths are in pixels on screen, however far the drawing is
+/// zoomed, so thin lines stay visible across millions of units.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Style {
+    pub fill: Option<Rgb>,
+    pub stroke: Option<(Rgb, f64)>,
+    pub opacity: f64,
+}
+
+impl Style {
+    pub fn fill(rgb: Rgb) -> Self {
+        Style {
+            fill: Some(rgb),
+            stroke: None,
+            opacity: 1.0,
+        }
+    }
+
+    pub fn stroke(rgb: Rgb, width: f64) -> Self {
+        Style {
+            fill: None,
+            stroke: Some((rgb, width)),
+            opacity: 1.0,
+        }
+    }
+
+    pub fn opacity(self, opacity: f64) -> Self {
+        Style { opacity, ..self }
+    }
+}
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.fill {
+            Some(rgb) => write!(f, r#"fill="{}""#, rgb.hex())?,
+            None => f.write_str(r#"fill="none""#)?,
+        }
+        if let Some((rgb, width)) = self.stroke {
+            write!(
+                f,
+                r#" stroke="{}" stroke-width="{}" vector-effect="non-scaling-stroke""#,
+                rgb.hex(),
+                width
+            )?;
+        }
+        if self.opacity < 1.0 {
+            write!(f, r#" opacity="{}""#, self.opacity)?;
+        }
+        Ok(())
+    }
+}
+
+/// A drawing in the puzzle's own coordinates, built a shape at a time and written out as SVG.
+/// Its view grows to take in every shape, with a margin around them.
+#[derive(Clone, Debug, Default)]
+pub struct Svg {
+    corners: Option<(Vec2, Vec2)>,
+    background: Option<Rgb>,
+    shapes: Vec<String>,
+}
+
+impl Svg {
+    pub fn new() -> Self {
+        Svg::default()
+    }
+
+    pub fn background(&mut self, rgb: Rgb) -> &mut Self {
+        self.background = Some(rgb);
+        self
+    }
+
+    /// A polygon through `points`, closed back to the first.
+    pub fn polygon(&mut self, points: &[Vec2], style: Style) -> &mut Self {
+        self.take_in(points);
+        let shape = format!(r#"<polygon points="{}" {}/>"#, list(points), style);
+        self.shapes.push(shape);
+        self
+    }
+
+    /// A line through `points`, left open.
+    pub fn polyline(&mut self, points: &[Vec2], style: Style) -> &mut Self {
+        self.take_in(points);
+        let shape = format!(r#"<polyline points="{}" {}/>"#, list(points), style);
+        self.shapes.push(shape);
+        self
+    }
+
+    /// A rectangle from `top_left`, `size` across and down.
+    pub fn rect(&mut self, top_left: Vec2, size: Vec2, style: Style) -> &mut Self {
+        self.take_in(&[top_left, top_left + size]);
+        let shape = format!(
+            r#"<rect x="{}" y="{}" width="{}" height="{}" {}/>"#,
+            top_left.x, top_left.y, size.x, size.y, style
+        );
+        self.shapes.push(shape);
+        self
+    }
+
+    /// A square covering the cell at `p`, as a grid of unit cells would draw it.
+    pub fn cell(&mut self, p: Vec2, style: Style) -> &mut Self {
+        self.rect(p, Vec2::new(1, 1), style)
+    }
+
+    pub fn circle(&mut self, centre: Vec2, radius: f64, style: Style) -> &mut Self {
+        let r = radius.ceil() as isize;
+        self.take_in(&[centre - Vec2::new(r, r), centre + Vec2::new(r, r)]);
+        let shape = format!(
+            r#"<circle cx="{}" cy="{}" r="{}" {}/>"#,
+            centre.x, centre.y, radius, style
+        );
+        self.shapes.push(shape);
+        self
+    }
+
+    /// The corners of the view, before the margin.
+    pub fn corners(&self) -> Option<(Vec2, Vec2)> {
+        self.corners
+    }
+
+    fn take_in(&mut self, points: &[Vec2]) {
+        for p in points {
+            let (min, max) = self.corners.unwrap_or((*p, *p));
+            self.corners = Some((
+                Vec2::new(min.x.min(p.x), min.y.min(p.y)),
+                Vec2::new(max.x.max(p.x), max.y.max(p.y)),
+            ));
+        }
+    }
+}
+
+/// Points as SVG lists them: "x,y x,y ...".
+fn list(points: &[Vec2]) -> String {
+    let mut out = String::new();
+    for (i, p) in points.iter().enumerate() {
+        if i > 0 {
+            out.push(' ');
+        }
+        let _ = write!(out, "{},{}", p.x, p.y);
+    }
+    out
+}
+
+impl fmt::Display for Svg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (min, max) = self.corners.unwrap_or_default();
+        let size = max - min;
+        let margin = (size.x.max(size.y) / 50).max(1);
+        let (min, size) = (
+            min - Vec2::new(margin, margin),
+            size + Vec2::new(2 * margin, 2 * margin),
+        );
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+            min.x, min.y, size.x, size.y
+        )?;
+        if let Some(rgb) = self.background {
+            writeln!(
+                f,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                min.x,
+                min.y,
+                size.x,
+                size.y,
+                rgb.hex()
+            )?;
+        }
+        for shape in &self.shapes {
+            writeln!(f, "{}", shape)?;
+        }
+        writeln!(f, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_style() {
+        let red = Rgb(255, 0, 0);
+        assert_eq!(Style::fill(red).to_string(), r##"fill="#ff0000""##);
+        assert_eq!(
+            Style::stroke(red, 2.0).opacity(0.5).to_string(),
+            r##"fill="none" stroke="#ff0000" stroke-width="2" vector-effect="non-scaling-stroke" opacity="0.5""##
+        );
+    }
+
+    #[test]
+    fn test_svg() {
+        let mut svg = Svg::new();
+        let diamond = [
+            Vec2::new(0, -2),
+            Vec2::new(2, 0),
+            Vec2::new(0, 2),
+            Vec2::new(-2, 0),
+        ];
+        svg.polygon(&diamond, Style::fill(Rgb::WHITE))
+            .cell(Vec2::new(3, 3), Style::fill(Rgb::BLACK));
+        assert_eq!(svg.corners(), Some((Vec2::new(-2, -2), Vec2::new(4, 4))));
+
+        let drawn = svg.to_string();
+        let lines = drawn.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[0],
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-3 -3 8 8">"#
+        );
+        assert_eq!(
+            lines[1],
+            r##"<polygon points="0,-2 2,0 0,2 -2,0" fill="#ffffff"/>"##
+        );
+        assert_eq!(
+            lines[2],
+            r##"<rect x="3" y="3" width="1" height="1" fill="#000000"/>"##
+        );
+        assert_eq!(lines[3], "</svg>");
+    }
+}
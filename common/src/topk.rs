@@ -0,0 +1,268 @@
+use std::cmp::Ordering;
+
+/// How `TopK::new` and `TopK::smallest` rank items.
+pub type Compare<T> = fn(&T, &T) -> Ordering;
+
+/// Keeps the best `k` of the items pushed into it, by a comparator where greater is better.
+/// The worst item kept sits at the root of a heap, so each push takes O(log k). Between equal
+/// items, the one pushed first counts as better, so is the one kept.
+#[derive(Clone)]
+pub struct TopK<T, C = Compare<T>> {
+    /// Each item with when it was pushed, as a heap with the worst at the root
+    heap: Vec<(T, u64)>,
+    capacity: usize,
+    cmp: C,
+    pushed: u64,
+}
+
+impl<T: Ord> TopK<T> {
+    /// Keeps the `k` largest items.
+    pub fn new(k: usize) -> Self {
+        TopK::by(k, T::cmp)
+    }
+
+    /// Keeps the `k` smallest items.
+    pub fn smallest(k: usize) -> Self {
+        TopK::by(k, |a, b| b.cmp(a))
+    }
+}
+
+impl<T> TopK<T> {
+    /// Keeps the `k` items with the largest keys.
+    pub fn by_key<K, F>(k: usize, key: F) -> TopK<T, impl Fn(&T, &T) -> Ordering>
+    where
+        K: Ord,
+        F: Fn(&T) -> K,
+    {
+        TopK::by(k, move |a, b| key(a).cmp(&key(b)))
+    }
+}
+
+impl<T, C> TopK<T, C>
+where
+    C: Fn(&T, &T) -> Ordering,
+{
+    /// Keeps the `k` greatest items by `cmp`.
+    pub fn by(k: usize, cmp: C) -> Self {
+        TopK {
+            heap: Vec::with_capacity(k.min(1024)),
+            capacity: k,
+            cmp,
+            pushed: 0,
+        }
+    }
+
+    /// How many items it keeps at most.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// The item a new one has to beat to be kept: the worst of the `k` kept. None until there
+    /// are `k`, as until then anything is kept.
+    pub fn threshold(&self) -> Option<&T> {
+        if self.capacity > 0 && self.heap.len() == self.capacity {
+            self.heap.first().map(|(item, _)| item)
+        } else {
+            None
+        }
+    }
+
+    /// Adds `item`, returning whichever item no longer makes the cut: the one it pushed out,
+    /// or `item` itself.
+    pub fn push(&mut self, item: T) -> Option<T> {
+        let entry = (item, self.pushed);
+        self.pushed += 1;
+        if self.heap.len() < self.capacity {
+            self.heap.push(entry);
+            self.sift_up(self.heap.len() - 1);
+            return None;
+        }
+        match self.heap.first() {
+            // Ties go to the item already kept, as it was pushed first
+            Some(worst) if (self.cmp)(&entry.0, &worst.0) == Ordering::Greater => {
+                let (out, _) = std::mem::replace(&mut self.heap[0], entry);
+                self.sift_down(0);
+                Some(out)
+            }
+            _ => Some(entry.0),
+        }
+    }
+
+    /// Adds everything `other` kept, as if its items had been pushed after this one's. Merging
+    /// the top k of each part of a sequence, in order, gives the top k of the whole.
+    pub fn merge(&mut self, other: Self) {
+        self.extend(other.into_sorted_vec());
+    }
+
+    /// The items kept, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.heap.iter().map(|(item, _)| item)
+    }
+
+    /// The items kept, best first, with equal items in the order they were pushed.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let (mut heap, cmp) = (self.heap, self.cmp);
+        heap.sort_by(|a, b| cmp(&b.0, &a.0).then(a.1.cmp(&b.1)));
+        heap.into_iter().map(|(item, _)| item).collect()
+    }
+
+    /// Whether the entry at `i` ranks below the one at `j`.
+    fn worse(&self, i: usize, j: usize) -> bool {
+        let ((a, a_pushed), (b, b_pushed)) = (&self.heap[i], &self.heap[j]);
+        match (self.cmp)(a, b) {
+            Ordering::Less => true,
+            Ordering::Equal => a_pushed > b_pushed,
+            Ordering::Greater => false,
+        }
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if !self.worse(i, parent) {
+                break;
+            }
+            self.heap.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut worst = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < self.heap.len() && self.worse(child, worst) {
+                    worst = child;
+                }
+            }
+            if worst == i {
+                break;
+            }
+            self.heap.swap(i, worst);
+            i = worst;
+        }
+    }
+}
+
+impl<T, C> Extend<T> for TopK<T, C>
+where
+    C: Fn(&T, &T) -> Ordering,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+/// Collecting keeps every item, largest first, as there's no `k` to stop at. Make one with
+/// `TopK::new` and `extend` it to keep only the best.
+impl<T: Ord> FromIterator<T> for TopK<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut top = TopK::new(usize::MAX);
+        top.extend(iter);
+        top
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_largest() {
+        let mut top = TopK::new(3);
+        assert_eq!(top.push(1), None);
+        top.extend([5, 4, 2]);
+        assert_eq!(top.len(), 3);
+        assert_eq!(top.threshold(), Some(&2));
+        assert_eq!(top.push(3), Some(2));
+        assert_eq!(top.push(0), Some(0));
+        assert_eq!(top.into_sorted_vec(), [5, 4, 3]);
+
+        let mut top = TopK::smallest(2);
+        top.extend([5, 1, 4, 2, 3]);
+        assert_eq!(top.into_sorted_vec(), [1, 2]);
+    }
+
+    #[test]
+    fn test_threshold() {
+        let mut top = TopK::new(2);
+        top.push(7);
+        // Anything gets in until it's full
+        assert_eq!(top.threshold(), None);
+        top.push(3);
+        assert_eq!(top.threshold(), Some(&3));
+
+        let mut none = TopK::new(0);
+        assert_eq!(none.push(1), Some(1));
+        assert!(none.is_empty());
+        assert_eq!(none.threshold(), None);
+    }
+
+    #[test]
+    fn test_ties() {
+        // Only the key counts, so the first pushed of each length wins
+        let mut top = TopK::by_key(3, |s: &&str| s.len());
+        top.extend(["bb", "a", "cc", "dd", "e", "ff"]);
+        assert_eq!(top.push("gg"), Some("gg"));
+        assert_eq!(top.push("hhh"), Some("dd"));
+        assert_eq!(top.into_sorted_vec(), ["hhh", "bb", "cc"]);
+
+        let mut top = TopK::by(4, |a: &(u8, char), b: &(u8, char)| a.0.cmp(&b.0));
+        top.extend([(1, 'a'), (2, 'b'), (1, 'c'), (2, 'd'), (1, 'e')]);
+        assert_eq!(
+            top.into_sorted_vec(),
+            [(2, 'b'), (2, 'd'), (1, 'a'), (1, 'c')]
+        );
+    }
+
+    #[test]
+    fn test_against_sorting() {
+        // A spread of values with plenty of repeats, checked against sorting the lot
+        let values = (0..200u64).map(|i| (i * 7919 % 101, i)).collect::<Vec<_>>();
+        for k in [0, 1, 2, 10, 50, 199, 200, 500] {
+            let mut top = TopK::by_key(k, |v: &(u64, u64)| v.0);
+            top.extend(values.iter().copied());
+            let mut sorted = values.clone();
+            sorted.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+            sorted.truncate(k);
+            assert_eq!(top.into_sorted_vec(), sorted, "k = {}", k);
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        // Split anywhere, the parts' tops merge into the whole's, ties and all
+        let values = (0..100u64).map(|i| (i * 37 % 23, i)).collect::<Vec<_>>();
+        let top_of = |values: &[(u64, u64)]| {
+            let mut top = TopK::by(5, |a: &(u64, u64), b: &(u64, u64)| a.0.cmp(&b.0));
+            top.extend(values.iter().copied());
+            top
+        };
+        let whole = top_of(&values).into_sorted_vec();
+        for parts in [1, 2, 3, 7, 100] {
+            let mut chunks = values.chunks(values.len().div_ceil(parts));
+            let mut merged = top_of(chunks.next().unwrap());
+            for chunk in chunks {
+                merged.merge(top_of(chunk));
+            }
+            assert_eq!(merged.into_sorted_vec(), whole, "{} parts", parts);
+        }
+    }
+
+    #[test]
+    fn test_collect() {
+        let top = [3, 1, 2].into_iter().collect::<TopK<_>>();
+        assert_eq!(top.iter().count(), 3);
+        assert_eq!(top.into_sorted_vec(), [3, 2, 1]);
+    }
+}
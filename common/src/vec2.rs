@@ -0,0 +1,281 @@
+use core::fmt;
+use std::{
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::parse::{parse_number, ParseError, ParseErrorKind};
+
+/// An integer 2D vector, used both for positions and for offsets between them.
+///
+/// The type has no opinion on which way `y` points; `Direction` follows day 9 and
+/// treats "U" as +y.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+/// The four orthogonal steps, as written in puzzle inputs ("U", "R", "D", "L").
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+const NEIGHBOURS_4: [Vec2; 4] = [
+    Vec2 { x: 1, y: 0 },
+    Vec2 { x: 0, y: 1 },
+    Vec2 { x: -1, y: 0 },
+    Vec2 { x: 0, y: -1 },
+];
+
+const NEIGHBOURS_8: [Vec2; 8] = [
+    Vec2 { x: 1, y: 0 },
+    Vec2 { x: 1, y: 1 },
+    Vec2 { x: 0, y: 1 },
+    Vec2 { x: -1, y: 1 },
+    Vec2 { x: -1, y: 0 },
+    Vec2 { x: -1, y: -1 },
+    Vec2 { x: 0, y: -1 },
+    Vec2 { x: 1, y: -1 },
+];
+
+impl Vec2 {
+    pub fn new(x: isize, y: isize) -> Self {
+        Vec2 { x, y }
+    }
+
+    pub fn origin() -> Self {
+        Vec2::new(0, 0)
+    }
+
+    /// Unit step (per axis) that moves `self` towards `v`.
+    pub fn direction_to(&self, v: &Vec2) -> Vec2 {
+        (*v - *self).signum()
+    }
+
+    pub fn signum(&self) -> Vec2 {
+        Vec2::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn abs(&self) -> Vec2 {
+        Vec2::new(self.x.abs(), self.y.abs())
+    }
+
+    pub fn manhattan(&self, v: &Vec2) -> usize {
+        self.x.abs_diff(v.x) + self.y.abs_diff(v.y)
+    }
+
+    pub fn chebyshev(&self, v: &Vec2) -> usize {
+        self.x.abs_diff(v.x).max(self.y.abs_diff(v.y))
+    }
+
+    /// Quarter turn from +x towards +y.
+    pub fn rotate_left(&self) -> Vec2 {
+        Vec2::new(-self.y, self.x)
+    }
+
+    /// Quarter turn from +y towards +x.
+    pub fn rotate_right(&self) -> Vec2 {
+        Vec2::new(self.y, -self.x)
+    }
+
+    /// The four orthogonally adjacent positions.
+    pub fn neighbours4(&self) -> impl Iterator<Item = Vec2> {
+        let p = *self;
+        NEIGHBOURS_4.into_iter().map(move |d| p + d)
+    }
+
+    /// The eight adjacent positions, including diagonals.
+    pub fn neighbours8(&self) -> impl Iterator<Item = Vec2> {
+        let p = *self;
+        NEIGHBOURS_8.into_iter().map(move |d| p + d)
+    }
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+}
+
+impl From<Direction> for Vec2 {
+    fn from(d: Direction) -> Vec2 {
+        match d {
+            Direction::Up => Vec2::new(0, 1),
+            Direction::Right => Vec2::new(1, 0),
+            Direction::Down => Vec2::new(0, -1),
+            Direction::Left => Vec2::new(-1, 0),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "U" => Ok(Direction::Up),
+            "R" => Ok(Direction::Right),
+            "D" => Ok(Direction::Down),
+            "L" => Ok(Direction::Left),
+            _ => Err(ParseError::in_line(ParseErrorKind::Unexpected, s, s)),
+        }
+    }
+}
+
+/// Parses the "x,y" coordinate form.
+impl FromStr for Vec2 {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = s
+            .split_once(',')
+            .ok_or_else(|| ParseError::in_line(ParseErrorKind::Expected("x,y"), s, s))?;
+        Ok(Vec2::new(
+            parse_number(s, x.trim())?,
+            parse_number(s, y.trim())?,
+        ))
+    }
+}
+
+impl From<(isize, isize)> for Vec2 {
+    fn from(item: (isize, isize)) -> Vec2 {
+        Vec2 {
+            x: item.0,
+            y: item.1,
+        }
+    }
+}
+
+impl From<Vec2> for (isize, isize) {
+    fn from(v: Vec2) -> Self {
+        (v.x, v.y)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, v: Vec2) -> Vec2 {
+        Vec2::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, v: Vec2) {
+        *self = *self + v;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, v: Vec2) -> Vec2 {
+        Vec2::new(self.x - v.x, self.y - v.y)
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, v: Vec2) {
+        *self = *self - v;
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<isize> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, k: isize) -> Vec2 {
+        Vec2::new(self.x * k, self.y * k)
+    }
+}
+
+impl fmt::Display for Vec2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!("({}, {})", self.x, self.y))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ops() {
+        let a = Vec2::new(1, 2);
+        let b = Vec2::new(3, -4);
+        assert_eq!(a + b, Vec2::new(4, -2));
+        assert_eq!(a - b, Vec2::new(-2, 6));
+        assert_eq!(-a, Vec2::new(-1, -2));
+        assert_eq!(b * 3, Vec2::new(9, -12));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Vec2::new(2, 18);
+        let b = Vec2::new(-2, 15);
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(a.direction_to(&b), Vec2::new(-1, -1));
+        assert_eq!(a.direction_to(&a), Vec2::origin());
+    }
+
+    #[test]
+    fn test_rotate() {
+        let v = Vec2::new(1, 0);
+        assert_eq!(v.rotate_left(), Vec2::new(0, 1));
+        assert_eq!(v.rotate_right(), Vec2::new(0, -1));
+        assert_eq!(v.rotate_left().rotate_left(), -v);
+        assert_eq!(v.rotate_left().rotate_right(), v);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let p = Vec2::new(5, 5);
+        let n4 = p.neighbours4().collect::<Vec<_>>();
+        assert_eq!(n4.len(), 4);
+        assert!(n4.iter().all(|n| n.manhattan(&p) == 1));
+
+        let n8 = p.neighbours8().collect::<Vec<_>>();
+        assert_eq!(n8.len(), 8);
+        assert!(n8.iter().all(|n| n.chebyshev(&p) == 1));
+    }
+
+    #[test]
+    fn test_parse() -> Result<(), ParseError> {
+        assert_eq!("498,4".parse::<Vec2>()?, Vec2::new(498, 4));
+        assert_eq!("-2, 15".parse::<Vec2>()?, Vec2::new(-2, 15));
+        assert!("498".parse::<Vec2>().is_err());
+        let e = "12, a".parse::<Vec2>().unwrap_err();
+        assert_eq!((e.kind, e.column), (ParseErrorKind::InvalidNumber, 5));
+
+        let dirs = ["U", "R", "D", "L"]
+            .into_iter()
+            .map(|s| s.parse::<Direction>())
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(dirs, Direction::ALL);
+        assert_eq!(Vec2::from(Direction::Up), Vec2::new(0, 1));
+        assert!("X".parse::<Direction>().is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,303 @@
+//! Reading inventories too big to read in one go: the input is cut into chunks at blank lines,
+//! each chunk is read on its own thread, and the chunks' elves, and the top totals among them,
+//! are put back together in order.
+
+use crate::{
+    for_each_elf,
+    inventory::{Elf, Inventory},
+    ElfError,
+};
+use anyhow::Result;
+use common::TopK;
+use rayon::prelude::*;
+use std::{
+    cmp::Ordering,
+    io::{self, Read},
+};
+use tracing::debug;
+
+/// About how much of the input each thread reads at once.
+const CHUNK_SIZE: usize = 1 << 22;
+
+/// The inventory read from `reader`, knowing which of its elves carry the `k` largest totals.
+pub fn inventory<R: Read>(reader: R, k: usize) -> Result<Inventory> {
+    inventory_in(Chunks::new(reader, CHUNK_SIZE), k)
+}
+
+/// An elf's total and where it comes in its chunk.
+type Total = (u64, usize);
+
+/// Ranks totals by size alone, so that equal ones go to whichever was pushed first.
+fn by_total(a: &Total, b: &Total) -> Ordering {
+    a.0.cmp(&b.0)
+}
+
+/// One chunk's elves, and the `k` among them carrying most.
+struct ChunkElves {
+    elves: Vec<(Vec<u64>, u64)>,
+    top: TopK<Total>,
+}
+
+fn inventory_in<R: Read>(chunks: Chunks<R>, k: usize) -> Result<Inventory> {
+    let mut elves = Vec::new();
+    let mut top: TopK<Total> = TopK::by(k, by_total);
+    let chunk_elves = |chunk: &Chunk| {
+        let mut part = ChunkElves {
+            elves: Vec::new(),
+            top: TopK::by(k, by_total),
+        };
+        let n = for_each_elf(chunk.text.lines(), chunk.first_line, |items, total| {
+            part.top.push((total, part.elves.len()));
+            part.elves.push((items.to_vec(), total));
+        })?;
+        Ok((part, n))
+    };
+    for_each_chunk(chunks, chunk_elves, |part, before| {
+        // The chunk's top k are the only ones of its elves that can be in the whole input's
+        let best = part.top.into_sorted_vec();
+        top.extend(best.into_iter().map(|(total, i)| (total, before + i)));
+        for (items, total) in part.elves {
+            let index = elves.len();
+            elves.push(Elf {
+                index,
+                items,
+                total,
+            });
+        }
+    })?;
+    let top = top
+        .into_sorted_vec()
+        .into_iter()
+        .map(|(_, i)| i)
+        .collect::<Vec<_>>();
+    debug!(?top, "largest totals");
+    Ok(Inventory::new(elves, top))
+}
+
+/// Works out `part` of each chunk on its own thread, passing them to `merge` in input order
+/// with how many elves came before the chunk. `part` also says how many elves the chunk held,
+/// so that errors can be given the elf's place in the whole input.
+fn for_each_chunk<R, T, P, M>(mut chunks: Chunks<R>, part: P, mut merge: M) -> Result<()>
+where
+    R: Read,
+    T: Send,
+    P: Fn(&Chunk) -> Result<(T, usize), ElfError> + Sync,
+    M: FnMut(T, usize),
+{
+    // A batch of chunks per thread at a time, so memory stays bounded however big the input
+    let batch = rayon::current_num_threads();
+    let mut count = 0;
+    let mut elves = 0;
+    loop {
+        let texts = chunks
+            .by_ref()
+            .take(batch)
+            .collect::<io::Result<Vec<_>>>()?;
+        if texts.is_empty() {
+            break;
+        }
+        count += texts.len();
+        let parts = texts.par_iter().map(&part).collect::<Vec<_>>();
+        // In order, so an error is the first in the input, and ties go as they would in one pass
+        for result in parts {
+            let (result, n) = result.map_err(|e| e.offset_elves(elves))?;
+            merge(result, elves);
+            elves += n;
+        }
+    }
+    debug!(chunks = count, elves, "totalled in chunks");
+    Ok(())
+}
+
+/// Part of the input, holding whole inventories.
+#[derive(Debug, PartialEq, Eq)]
+struct Chunk {
+    text: String,
+    /// The number of the chunk's first line in the whole input
+    first_line: usize,
+}
+
+/// Reads the input a chunk of about `size` bytes at a time. Each chunk but the last ends where a
+/// blank line does, and that blank line is left out, so the chunks' inventories are exactly the
+/// input's. An inventory longer than `size` makes its chunk as long as it needs to be.
+struct Chunks<R> {
+    reader: R,
+    size: usize,
+    /// Read but not yet handed out
+    buf: Vec<u8>,
+    line: usize,
+    /// How much of `buf` is known to hold no blank line
+    searched: usize,
+    eof: bool,
+    finished: bool,
+}
+
+impl<R: Read> Chunks<R> {
+    fn new(reader: R, size: usize) -> Self {
+        Chunks {
+            reader,
+            size: size.max(1),
+            buf: Vec::new(),
+            line: 1,
+            searched: 0,
+            eof: false,
+            finished: false,
+        }
+    }
+
+    fn next_chunk(&mut self) -> io::Result<Option<Chunk>> {
+        let mut target = self.size;
+        loop {
+            if !self.eof && self.buf.len() < target {
+                let want = (target - self.buf.len()) as u64;
+                let got = (&mut self.reader).take(want).read_to_end(&mut self.buf)?;
+                self.eof = (got as u64) < want;
+                continue;
+            }
+            let bytes = match last_blank_line(&self.buf, self.searched) {
+                _ if self.eof => {
+                    // Whatever's left is the last chunk, even if that's nothing, which holds no elves
+                    if self.finished {
+                        return Ok(None);
+                    }
+                    self.finished = true;
+                    self.searched = 0;
+                    std::mem::take(&mut self.buf)
+                }
+                Some((end, next)) => {
+                    let rest = self.buf.split_off(next);
+                    self.buf.truncate(end);
+                    // Nothing after the last blank line is one
+                    self.searched = rest.len();
+                    std::mem::replace(&mut self.buf, rest)
+                }
+                None => {
+                    self.searched = self.buf.len();
+                    target += self.size;
+                    continue;
+                }
+            };
+            let first_line = self.line;
+            // Its lines, then the blank line after it
+            self.line += bytes.iter().filter(|b| **b == b'\n').count() + 1;
+            let text = String::from_utf8(bytes)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            return Ok(Some(Chunk { text, first_line }));
+        }
+    }
+}
+
+impl<R: Read> Iterator for Chunks<R> {
+    type Item = io::Result<Chunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_chunk().transpose()
+    }
+}
+
+/// Where the last complete blank line in `buf` is: the end of the line before it, and the start
+/// of the line after it. The first `searched` bytes are known to hold none, so only a blank line
+/// that one of them starts but didn't finish is looked for there.
+fn last_blank_line(buf: &[u8], searched: usize) -> Option<(usize, usize)> {
+    (searched.saturating_sub(2)..buf.len())
+        .rev()
+        .find_map(|i| match &buf[i..] {
+            [b'\n', b'\n', ..] => Some((i + 1, i + 2)),
+            [b'\n', b'\r', b'\n', ..] => Some((i + 1, i + 3)),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{generate, EXAMPLE};
+    use proptest::prelude::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn chunks(input: &str, size: usize) -> Vec<Chunk> {
+        Chunks::new(input.as_bytes(), size)
+            .collect::<io::Result<_>>()
+            .unwrap()
+    }
+
+    fn chunk(text: &str, first_line: usize) -> Chunk {
+        Chunk {
+            text: text.to_string(),
+            first_line,
+        }
+    }
+
+    fn indices(elves: Vec<&Elf>) -> Vec<usize> {
+        elves.into_iter().map(|e| e.index).collect()
+    }
+
+    #[test]
+    fn test_chunks() {
+        let input = "1\n2\n\n3\n\n4\n5\n6\n\n7";
+        assert_eq!(
+            chunks(input, 2),
+            [
+                chunk("1\n2\n", 1),
+                chunk("3\n", 4),
+                chunk("4\n5\n6\n", 6),
+                chunk("7", 10)
+            ]
+        );
+        assert_eq!(chunks(input, 100), [chunk(input, 1)]);
+
+        // A run of blank lines can be split between chunks, as any number of them separate elves
+        assert_eq!(
+            chunks("1\n\n\n2\r\n\r\n3\n", 3),
+            [chunk("1\n", 1), chunk("\n2\r\n", 3), chunk("3\n", 6)]
+        );
+        assert_eq!(chunks("", 4), [chunk("", 1)]);
+
+        // An inventory many times the chunk size, with its blank line split between reads
+        let long = format!("{}\r\n9", "8\n".repeat(50));
+        assert_eq!(
+            chunks(&long, 1),
+            [chunk(&"8\n".repeat(50), 1), chunk("9", 52)]
+        );
+    }
+
+    #[test]
+    fn test_inventory() -> Result<()> {
+        let input = generate::input(&mut StdRng::seed_from_u64(2), 300);
+        let expected = Inventory::parse_lines(input.lines())?;
+        for size in [1, 50, 1 << 20] {
+            let inventory = inventory_in(Chunks::new(input.as_bytes(), size), 3)?;
+            assert_eq!(inventory.elves(), expected.elves(), "chunks of {}", size);
+            assert_eq!(indices(inventory.top(3)), indices(expected.top(3)));
+        }
+
+        let inventory = inventory(EXAMPLE.as_bytes(), 2)?;
+        assert_eq!(indices(inventory.top(2)), [3, 2]);
+        // More than it kept track of while reading
+        assert_eq!(indices(inventory.top(3)), [3, 2, 4]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_errors() {
+        // The elf and line are counted from the start of the input, not of its chunk
+        let input = "1\n2\n\n3\n\nfour\n5";
+        let e = inventory_in(Chunks::new(input.as_bytes(), 2), 3).unwrap_err();
+        let e = e.downcast::<ElfError>().unwrap();
+        assert_eq!((e.elf, e.error.line), (2, 6));
+        assert_eq!(
+            e.to_string(),
+            "elf 2, line 6, column 1: invalid number \"four\""
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_one_pass(seed: u64, elves in 1..200usize, size in 1..256usize, k in 0..10usize) {
+            let input = generate::input(&mut StdRng::seed_from_u64(seed), elves);
+            let inventory = inventory_in(Chunks::new(input.as_bytes(), size), k).unwrap();
+            let one_pass = Inventory::parse_lines(input.lines()).unwrap();
+            prop_assert_eq!(indices(inventory.top(k)), indices(one_pass.top(k)));
+        }
+    }
+}
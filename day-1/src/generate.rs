@@ -0,0 +1,35 @@
+//! Random inventories, and a slow but obviously correct solver to check them against.
+
+use common::Answers;
+use rand::Rng;
+
+/// Calorie counts for `size` elves, each carrying a handful of items.
+pub fn input<R: Rng + ?Sized>(rng: &mut R, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let items = rng.random_range(1..=6);
+            (0..items)
+                .map(|_| rng.random_range(1..=60000).to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Sorts every elf's total.
+pub fn reference(input: &str) -> Answers {
+    let mut totals = input
+        .split("\n\n")
+        .map(|elf| elf.lines().map(|l| l.parse::<u64>().unwrap()).sum::<u64>())
+        .collect::<Vec<_>>();
+    totals.sort_by(|a, b| b.cmp(a));
+    Answers::new(totals[0], totals.iter().take(3).sum::<u64>())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    common::test_against_reference!(crate::Day1, 1..50usize);
+}
@@ -0,0 +1,285 @@
+//! Every elf's items, kept so that questions beyond the puzzle's can be asked of them.
+
+use crate::{for_each_elf, ElfError};
+use anyhow::{anyhow, Result};
+use common::{Interval, TopK};
+use serde::Serialize;
+use std::{collections::BTreeMap, sync::OnceLock};
+
+/// One elf's inventory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Elf {
+    /// Where the elf comes in the input, from 0
+    pub index: usize,
+    /// The calories of each item it carries
+    pub items: Vec<u64>,
+    /// The calories of all of them, which parsing checks fit in a u64
+    pub total: u64,
+}
+
+/// An elf and its total, as the details report them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Ranked {
+    pub elf: usize,
+    pub total: u64,
+}
+
+impl From<&Elf> for Ranked {
+    fn from(elf: &Elf) -> Self {
+        Ranked {
+            elf: elf.index,
+            total: elf.total,
+        }
+    }
+}
+
+/// How many elves' totals fall in a range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bucket {
+    pub range: Interval<u64>,
+    pub elves: usize,
+}
+
+/// Every elf's inventory, in input order.
+#[derive(Clone, Debug)]
+pub struct Inventory {
+    elves: Vec<Elf>,
+    /// Indices into `elves` of however many of the elves carrying most parsing found, most
+    /// first, with ties in input order
+    top: Vec<usize>,
+    /// Indices into `elves`, most calories first, with ties in input order, once a question
+    /// needs every elf ranked
+    ranked: OnceLock<Vec<usize>>,
+}
+
+impl Inventory {
+    /// An inventory of `elves`, in input order, as parsing reads them, with `top` the indices of
+    /// those carrying most, if parsing found them.
+    pub(crate) fn new(elves: Vec<Elf>, top: Vec<usize>) -> Self {
+        Inventory {
+            elves,
+            top,
+            ranked: OnceLock::new(),
+        }
+    }
+
+    /// Reads inventories separated by blank lines.
+    pub fn parse_lines<I, S>(lines: I) -> Result<Self, ElfError>
+    where
+        I: Iterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut elves = Vec::new();
+        for_each_elf(lines, 1, |items, total| {
+            elves.push(Elf {
+                index: elves.len(),
+                items: items.to_vec(),
+                total,
+            })
+        })?;
+        Ok(Inventory::new(elves, Vec::new()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.elves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elves.is_empty()
+    }
+
+    pub fn elves(&self) -> &[Elf] {
+        &self.elves
+    }
+
+    /// The `k` elves carrying the most, most first. Between equal totals the earlier elf wins.
+    pub fn top(&self, k: usize) -> Vec<&Elf> {
+        if k <= self.top.len() || self.top.len() == self.len() {
+            return self.top.iter().take(k).map(|i| &self.elves[*i]).collect();
+        }
+        let mut top = TopK::by_key(k, |elf: &&Elf| elf.total);
+        top.extend(&self.elves);
+        top.into_sorted_vec()
+    }
+
+    /// The `k` elves carrying the least, least first. Between equal totals the earlier elf wins.
+    pub fn bottom(&self, k: usize) -> Vec<&Elf> {
+        let mut bottom = TopK::by_key(k, |elf: &&Elf| std::cmp::Reverse(elf.total));
+        bottom.extend(&self.elves);
+        bottom.into_sorted_vec()
+    }
+
+    /// The elf at `rank`, where the one carrying the most is rank 1.
+    pub fn by_rank(&self, rank: usize) -> Option<&Elf> {
+        let ranked = self.ranked.get_or_init(|| {
+            let mut ranked = (0..self.len()).collect::<Vec<_>>();
+            ranked.sort_by_key(|i| std::cmp::Reverse(self.elves[*i].total));
+            ranked
+        });
+        let i = ranked.get(rank.checked_sub(1)?)?;
+        Some(&self.elves[*i])
+    }
+
+    /// The average total.
+    pub fn mean(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+        let sum = self.elves.iter().map(|e| e.total as f64).sum::<f64>();
+        Some(sum / self.len() as f64)
+    }
+
+    /// The middle total, or the average of the middle two.
+    pub fn median(&self) -> Option<f64> {
+        let n = self.len();
+        let middle = |rank| self.by_rank(rank).map(|e| e.total as f64);
+        if n % 2 == 1 {
+            middle(n / 2 + 1)
+        } else {
+            Some((middle(n / 2)? + middle(n / 2 + 1)?) / 2.0)
+        }
+    }
+
+    /// The smallest total that at least `p` percent of elves carry no more than (the
+    /// nearest-rank method), so the 100th percentile is the largest.
+    pub fn percentile(&self, p: u32) -> Result<Option<u64>> {
+        if p > 100 {
+            return Err(anyhow!("Percentile {} is over 100", p));
+        }
+        // Counted up from the smallest
+        let from_bottom = (p as usize * self.len()).div_ceil(100).max(1);
+        let rank = (self.len() + 1).saturating_sub(from_bottom);
+        Ok(self.by_rank(rank).map(|e| e.total))
+    }
+
+    /// How many elves' totals fall in each range of `width` calories that any do, smallest
+    /// first. Ranges start at multiples of `width`.
+    pub fn histogram(&self, width: u64) -> Result<Vec<Bucket>> {
+        if width == 0 {
+            return Err(anyhow!("Histogram buckets can't be 0 calories wide"));
+        }
+        let mut counts = BTreeMap::new();
+        for elf in &self.elves {
+            *counts.entry(elf.total / width).or_insert(0) += 1;
+        }
+        Ok(counts
+            .into_iter()
+            .map(|(bucket, elves)| {
+                let lower = bucket * width;
+                // The last range stops at u64::MAX rather than overflowing
+                let upper = lower.saturating_add(width - 1);
+                Bucket {
+                    range: Interval::new(lower, upper),
+                    elves,
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::EXAMPLE;
+
+    fn example() -> Inventory {
+        Inventory::parse_lines(EXAMPLE.lines()).unwrap()
+    }
+
+    /// Elves carrying one item each, of `totals`.
+    fn carrying(totals: &[u64]) -> Inventory {
+        let elves = totals.iter().enumerate().map(|(index, total)| Elf {
+            index,
+            items: vec![*total],
+            total: *total,
+        });
+        Inventory::new(elves.collect(), Vec::new())
+    }
+
+    fn indices(elves: Vec<&Elf>) -> Vec<usize> {
+        elves.into_iter().map(|e| e.index).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let inventory = example();
+        assert_eq!(inventory.len(), 5);
+        assert_eq!(
+            inventory.elves()[3],
+            Elf {
+                index: 3,
+                items: vec![7000, 8000, 9000],
+                total: 24000
+            }
+        );
+        let e = Inventory::parse_lines("1\n\nx".lines()).unwrap_err();
+        assert_eq!((e.elf, e.error.line), (1, 3));
+    }
+
+    #[test]
+    fn test_ranks() {
+        let inventory = example();
+        assert_eq!(indices(inventory.top(3)), [3, 2, 4]);
+        assert_eq!(indices(inventory.bottom(2)), [1, 0]);
+        assert_eq!(inventory.by_rank(1).map(|e| e.total), Some(24000));
+        assert_eq!(inventory.by_rank(5).map(|e| e.index), Some(1));
+        assert_eq!(inventory.by_rank(0), None);
+        assert_eq!(inventory.by_rank(6), None);
+
+        // Ties go to the earlier elf, from both ends
+        let tied = carrying(&[5, 5, 2, 2]);
+        assert_eq!(indices(tied.top(1)), [0]);
+        assert_eq!(indices(tied.bottom(1)), [2]);
+        for rank in 1..=4 {
+            assert_eq!(tied.by_rank(rank), tied.top(4).get(rank - 1).copied());
+        }
+    }
+
+    #[test]
+    fn test_averages() -> Result<()> {
+        let inventory = example();
+        assert_eq!(inventory.mean(), Some(11000.0));
+        assert_eq!(inventory.median(), Some(10000.0));
+        let even = carrying(&[1, 4, 2, 8]);
+        assert_eq!(even.median(), Some(3.0));
+
+        assert_eq!(inventory.percentile(0)?, Some(4000));
+        assert_eq!(inventory.percentile(20)?, Some(4000));
+        assert_eq!(inventory.percentile(21)?, Some(6000));
+        assert_eq!(inventory.percentile(50)?, Some(10000));
+        assert_eq!(inventory.percentile(100)?, Some(24000));
+        assert!(inventory.percentile(101).is_err());
+
+        let empty = carrying(&[]);
+        assert_eq!((empty.mean(), empty.median()), (None, None));
+        assert_eq!(empty.percentile(50)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_histogram() -> Result<()> {
+        let buckets = example()
+            .histogram(5000)?
+            .into_iter()
+            .map(|b| (b.range.to_string(), b.elves))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            buckets,
+            [
+                ("0-4999".to_string(), 1),
+                ("5000-9999".to_string(), 1),
+                ("10000-14999".to_string(), 2),
+                ("20000-24999".to_string(), 1),
+            ]
+        );
+        assert_eq!(example().histogram(100000)?.len(), 1);
+
+        // Only the buckets with elves in, however far apart
+        let spread = carrying(&[1, 99999999999999]).histogram(1)?;
+        assert_eq!(spread.len(), 2);
+        assert_eq!(spread[1].range, Interval::single(99999999999999));
+        assert!(example().histogram(0).is_err());
+        assert!(carrying(&[]).histogram(10)?.is_empty());
+        Ok(())
+    }
+}
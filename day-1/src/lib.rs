@@ -0,0 +1,352 @@
+pub mod chunked;
+pub mod generate;
+pub mod inventory;
+
+use anyhow::anyhow;
+use common::{parse::parse_number, Details, Example, ParseError, ParseErrorKind, Solution, TopK};
+use core::fmt;
+use inventory::{Inventory, Ranked};
+use serde::Deserialize;
+use serde_json::json;
+use std::io::BufRead;
+use tracing::debug;
+
+/// The `n` largest totals, largest first.
+pub fn top_n(totals: &[u64], n: usize) -> Vec<u64> {
+    let mut top = TopK::new(n);
+    top.extend(totals.iter().copied());
+    let top = top.into_sorted_vec();
+    debug!(n, ?top, "largest totals");
+    top
+}
+
+/// A mistake in an elf's inventory: which elf, counting from 0, and where in the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ElfError {
+    pub elf: usize,
+    pub error: ParseError,
+}
+
+impl ElfError {
+    /// Moves the elf along by `elves`, for errors counted from part way through the input.
+    pub fn offset_elves(mut self, elves: usize) -> Self {
+        self.elf += elves;
+        self
+    }
+}
+
+impl fmt::Display for ElfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!("elf {}, {}", self.elf, self.error))
+    }
+}
+
+impl std::error::Error for ElfError {}
+
+/// One item's calories, which can't be negative.
+fn parse_calories(line: &str) -> Result<u64, ParseError> {
+    match line.strip_prefix('-') {
+        Some(digits) if parse_number::<u64>(line, digits).is_ok() => Err(ParseError::in_line(
+            ParseErrorKind::Invalid("negative calories".to_string()),
+            line,
+            line,
+        )),
+        _ => parse_number(line, line),
+    }
+}
+
+/// Calls `f` with each elf's items and their total, in order, returning how many elves there
+/// were. Elves are separated by one or more blank lines, trailing whitespace is ignored, and the
+/// lines are numbered from `first_line` for errors.
+pub fn for_each_elf<I, S, F>(lines: I, first_line: usize, mut f: F) -> Result<usize, ElfError>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+    F: FnMut(&[u64], u64),
+{
+    let mut elves = 0;
+    let mut items = Vec::new();
+    let mut total = 0u64;
+
+    for (i, s) in lines.enumerate() {
+        let s = s.as_ref().trim_end();
+        if s.is_empty() {
+            if !items.is_empty() {
+                f(&items, total);
+                items.clear();
+                total = 0;
+                elves += 1;
+            }
+            continue;
+        }
+        let error = |error: ParseError| ElfError {
+            elf: elves,
+            error: error.at_line(first_line + i),
+        };
+        let calories = parse_calories(s).map_err(error)?;
+        total = total.checked_add(calories).ok_or_else(|| {
+            let overflow = ParseErrorKind::Invalid("too many calories to total".to_string());
+            error(ParseError::in_line(overflow, s, s))
+        })?;
+        items.push(calories);
+    }
+
+    if !items.is_empty() {
+        f(&items, total);
+        elves += 1;
+    }
+    Ok(elves)
+}
+
+/// Adds up `totals`, failing if they come to more than a u64 holds.
+pub fn sum_totals<I: IntoIterator<Item = u64>>(totals: I) -> anyhow::Result<u64> {
+    totals
+        .into_iter()
+        .try_fold(0u64, |sum, total| sum.checked_add(total))
+        .ok_or_else(|| anyhow!("The totals add up to too many calories"))
+}
+
+const EXAMPLE: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+/// The puzzle's settings.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    /// How many of the largest totals part 2 adds up
+    pub top: usize,
+    /// How many of the elves carrying least the details list
+    pub bottom: usize,
+    /// Which percentiles of the totals the details give
+    pub percentiles: Vec<u32>,
+    /// How wide the details' histogram of totals buckets them, if it's wanted
+    pub bucket: Option<u64>,
+    /// Which elf the details pick out by rank, where 1 carries the most
+    pub rank: Option<usize>,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            top: 3,
+            bottom: 0,
+            percentiles: Vec::new(),
+            bucket: None,
+            rank: None,
+        }
+    }
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Model = Inventory;
+    type Params = Params;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "24000", "45000")];
+
+    fn parse_lines<I>(lines: I) -> anyhow::Result<Self::Model>
+    where
+        I: Iterator<Item = String>,
+    {
+        let inventory = Inventory::parse_lines(lines)?;
+        debug!(elves = inventory.len(), "parsed");
+        Ok(inventory)
+    }
+
+    fn parse_with(input: &str, params: &Params) -> anyhow::Result<Self::Model> {
+        Self::parse_reader_with(input.as_bytes(), params)
+    }
+
+    /// Reads the input a chunk per thread, for inventories too big to want to go through a line
+    /// at a time, finding the elves part 2 adds up as it goes.
+    fn parse_reader_with<R: BufRead>(reader: R, params: &Params) -> anyhow::Result<Self::Model> {
+        let inventory = chunked::inventory(reader, params.top.max(1))?;
+        debug!(elves = inventory.len(), "parsed");
+        Ok(inventory)
+    }
+
+    fn part1(inventory: &Self::Model, _: &Params) -> anyhow::Result<u64> {
+        let most = *inventory
+            .top(1)
+            .first()
+            .ok_or_else(|| anyhow!("No elves in the input"))?;
+        Ok(most.total)
+    }
+
+    fn part2(inventory: &Self::Model, params: &Params) -> anyhow::Result<u64> {
+        sum_totals(inventory.top(params.top).iter().map(|e| e.total))
+    }
+
+    fn details(inventory: &Self::Model, params: &Params) -> anyhow::Result<Details> {
+        let ranked = |elves: Vec<&inventory::Elf>| {
+            serde_json::to_value(elves.into_iter().map(Ranked::from).collect::<Vec<_>>())
+        };
+        let top = inventory.top(params.top);
+        let mut details = Details::new();
+        details.insert(
+            "top".to_string(),
+            top.iter().map(|e| e.total).collect::<Vec<_>>().into(),
+        );
+        details.insert("top_elves".to_string(), ranked(top)?);
+        details.insert("mean".to_string(), inventory.mean().into());
+        details.insert("median".to_string(), inventory.median().into());
+        if params.bottom > 0 {
+            let bottom = inventory.bottom(params.bottom);
+            details.insert("bottom_elves".to_string(), ranked(bottom)?);
+        }
+        if !params.percentiles.is_empty() {
+            let mut percentiles = serde_json::Map::new();
+            for p in &params.percentiles {
+                percentiles.insert(p.to_string(), inventory.percentile(*p)?.into());
+            }
+            details.insert("percentiles".to_string(), percentiles.into());
+        }
+        if let Some(width) = params.bucket {
+            let histogram = inventory
+                .histogram(width)?
+                .into_iter()
+                .map(|b| json!({"from": b.range.lower(), "to": b.range.upper(), "elves": b.elves}))
+                .collect::<Vec<_>>();
+            details.insert("histogram".to_string(), histogram.into());
+        }
+        if let Some(rank) = params.rank {
+            let elf = inventory.by_rank(rank).ok_or_else(|| {
+                anyhow!("No elf at rank {}, as there are {}", rank, inventory.len())
+            })?;
+            let elf = json!({"elf": elf.index, "total": elf.total, "items": elf.items});
+            details.insert("rank".to_string(), elf);
+        }
+        Ok(details)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_top_n() {
+        let totals = [6000, 4000, 11000, 24000, 10000];
+        assert_eq!(top_n(&totals, 3), [24000, 11000, 10000]);
+        assert_eq!(top_n(&totals, 1), [24000]);
+        assert_eq!(top_n(&totals, 10).len(), 5);
+        assert!(top_n(&totals, 0).is_empty());
+    }
+
+    fn totals(input: &str) -> Result<Vec<u64>, ElfError> {
+        let mut totals = Vec::new();
+        for_each_elf(input.lines(), 1, |_, total| totals.push(total))?;
+        Ok(totals)
+    }
+
+    #[test]
+    fn test_totals() -> Result<(), ElfError> {
+        assert_eq!(totals(EXAMPLE)?, [6000, 4000, 11000, 24000, 10000]);
+        // Any number of blank lines separate elves, and trailing whitespace doesn't count
+        assert_eq!(totals("\n1 \n2\t\n\n\n  \n3\n\n")?, [3, 3]);
+        assert!(totals("")?.is_empty());
+        assert_eq!(totals("4294967296\n4294967296")?, [1 << 33]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_errors() {
+        let e = totals("1\n\n2\n-3").unwrap_err();
+        assert_eq!((e.elf, e.error.line), (1, 4));
+        assert_eq!(
+            e.to_string(),
+            "elf 1, line 4, column 1: negative calories \"-3\""
+        );
+
+        let e = totals("1\n\n\n18446744073709551615\n1").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "elf 1, line 5, column 1: too many calories to total \"1\""
+        );
+        let e = totals("1\n2 x").unwrap_err();
+        assert_eq!((e.elf, &e.error.kind), (0, &ParseErrorKind::InvalidNumber));
+        assert_eq!(
+            e.to_string(),
+            "elf 0, line 2, column 1: invalid number \"2 x\""
+        );
+
+        assert!(Day1::solve("\n\n").is_err());
+    }
+
+    #[test]
+    fn test_solve() -> anyhow::Result<()> {
+        let answers = Day1::solve(EXAMPLE)?;
+        assert_eq!(answers.part1, "24000");
+        assert_eq!(answers.part2, "45000");
+        assert_eq!(answers.details["top"].to_string(), "[24000,11000,10000]");
+
+        assert_eq!(answers.details["median"].to_string(), "10000.0");
+        assert!(!answers.details.contains_key("histogram"));
+
+        // Each elf's total fits, but the sum of the top ones doesn't
+        let max = format!("{}\n\n{}", u64::MAX, u64::MAX);
+        assert!(Day1::solve(&max).is_err());
+
+        let params = Params {
+            top: 2,
+            ..Params::default()
+        };
+        let answers = Day1::solve_with(EXAMPLE, &params)?;
+        assert_eq!(answers.part2, "35000");
+        assert_eq!(
+            answers.details["top_elves"].to_string(),
+            r#"[{"elf":3,"total":24000},{"elf":2,"total":11000}]"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_queries() -> anyhow::Result<()> {
+        let params = Params {
+            bottom: 1,
+            percentiles: vec![50, 90],
+            bucket: Some(10000),
+            rank: Some(2),
+            ..Params::default()
+        };
+        let details = Day1::solve_with(EXAMPLE, &params)?.details;
+        assert_eq!(
+            details["bottom_elves"].to_string(),
+            r#"[{"elf":1,"total":4000}]"#
+        );
+        assert_eq!(
+            details["percentiles"].to_string(),
+            r#"{"50":10000,"90":24000}"#
+        );
+        assert_eq!(
+            details["histogram"].to_string(),
+            r#"[{"elves":2,"from":0,"to":9999},{"elves":2,"from":10000,"to":19999},{"elves":1,"from":20000,"to":29999}]"#
+        );
+        assert_eq!(
+            details["rank"].to_string(),
+            r#"{"elf":2,"items":[5000,6000],"total":11000}"#
+        );
+
+        let params = Params {
+            rank: Some(6),
+            ..Params::default()
+        };
+        assert!(Day1::solve_with(EXAMPLE, &params).is_err());
+        Ok(())
+    }
+}
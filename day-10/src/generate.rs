@@ -0,0 +1,64 @@
+//! Random programs, and a slow but obviously correct solver to check them against.
+
+use common::Answers;
+use rand::Rng;
+
+/// A program that takes exactly the 240 cycles the CRT draws, adding values of up to `size`
+/// either way.
+pub fn input<R: Rng + ?Sized>(rng: &mut R, size: usize) -> String {
+    let size = size as i32;
+    let mut lines = Vec::new();
+    let mut cycles = 0;
+    while cycles < 240 {
+        if cycles < 239 && rng.random_bool(0.5) {
+            lines.push(format!("addx {}", rng.random_range(-size..=size)));
+            cycles += 2;
+        } else {
+            lines.push("noop".to_string());
+            cycles += 1;
+        }
+    }
+    lines.join("\n")
+}
+
+/// Writes down X during every cycle, then reads both parts off that.
+pub fn reference(input: &str) -> Answers {
+    let mut during = Vec::new();
+    let mut x = 1;
+    for line in input.lines() {
+        during.push(x);
+        if let Some(v) = line.strip_prefix("addx ") {
+            during.push(x);
+            x += v.parse::<i32>().unwrap();
+        }
+    }
+
+    let signal = (20..=220)
+        .step_by(40)
+        .map(|cycle| cycle as i32 * during[cycle - 1])
+        .sum::<i32>();
+    let screen = during
+        .chunks(40)
+        .map(|row| {
+            row.iter()
+                .enumerate()
+                .map(|(pos, x)| {
+                    if (x - pos as i32).abs() <= 1 {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    Answers::new(signal, screen)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    common::test_against_reference!(crate::Day10, 0..50usize);
+}
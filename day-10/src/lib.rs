@@ -0,0 +1,349 @@
+pub mod generate;
+
+use common::{
+    parse::{parse_lines, parse_number},
+    Example, NoParams, ParseError, ParseErrorKind, Solution,
+};
+use tracing::{debug, trace};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Instr {
+    Noop,
+    Addx(i32),
+}
+
+pub fn parse_instrs(line: &str) -> Result<Instr, ParseError> {
+    let words = line.split_whitespace().collect::<Vec<_>>();
+    match words.as_slice() {
+        ["noop"] => Ok(Instr::Noop),
+        ["addx", v] => Ok(Instr::Addx(parse_number(line, v)?)),
+        ["addx"] => Err(ParseError::in_line(
+            ParseErrorKind::Expected("a value to add"),
+            line,
+            "",
+        )),
+        [instr, ..] => Err(ParseError::in_line(ParseErrorKind::Unexpected, line, instr)),
+        [] => Err(ParseError::in_line(
+            ParseErrorKind::Expected("an instruction"),
+            line,
+            line,
+        )),
+    }
+}
+
+/// The handheld's CPU, driving a 40x6 CRT.
+pub struct Cpu {
+    tick: usize,
+    x: i32,
+    crt: [char; 260],
+}
+
+impl Cpu {
+    pub fn new() -> Self {
+        Cpu {
+            tick: 1,
+            x: 1,
+            crt: [' '; 260],
+        }
+    }
+
+    fn update_screen(&mut self) {
+        let crt_tick = self.tick - 1;
+        let beam = crt_tick as i32 % 40;
+        self.crt[crt_tick] = if beam > self.x - 2 && beam < self.x + 2 {
+            '#'
+        } else {
+            '.'
+        };
+    }
+
+    fn check_signal(&mut self) -> Option<i32> {
+        if (self.tick as i32 - 20) % 40 == 0 {
+            let signal_strength = self.tick as i32 * self.x;
+            trace!(cycle = self.tick, x = self.x, signal_strength);
+            Some(signal_strength)
+        } else {
+            None
+        }
+    }
+
+    /// Runs `instrs` to the end, returning the final X register and the total signal strength.
+    pub fn run<I>(&mut self, instrs: I) -> anyhow::Result<(i32, i32)>
+    where
+        I: Iterator<Item = Instr>,
+    {
+        let mut total_signal_strength = 0;
+        for instr in instrs {
+            match instr {
+                Instr::Noop => {
+                    self.update_screen();
+                    self.tick += 1;
+                    if let Some(signal_strength) = self.check_signal() {
+                        total_signal_strength += signal_strength;
+                    }
+                }
+                Instr::Addx(v) => {
+                    self.update_screen();
+                    self.tick += 1;
+                    if let Some(signal_strength) = self.check_signal() {
+                        total_signal_strength += signal_strength;
+                    }
+                    self.update_screen();
+                    self.tick += 1;
+                    self.x += v;
+                    if let Some(signal_strength) = self.check_signal() {
+                        total_signal_strength += signal_strength;
+                    }
+                }
+            }
+        }
+        debug!(cycles = self.tick - 1, x = self.x, "ran");
+        Ok((self.x, total_signal_strength))
+    }
+
+    /// What the CRT shows, one row per line.
+    pub fn screen(&self) -> String {
+        self.crt
+            .chunks(40)
+            .take(6)
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Default for Cpu {
+    fn default() -> Self {
+        Cpu::new()
+    }
+}
+
+const EXAMPLE: &str = r#"addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop"#;
+
+/// What the CRT shows after running the example.
+const EXAMPLE_SCREEN: &str = "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....";
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Model = Vec<Instr>;
+    type Params = NoParams;
+    type Part1 = i32;
+    type Part2 = String;
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "13140", EXAMPLE_SCREEN)];
+
+    fn parse_lines<I>(lines: I) -> anyhow::Result<Self::Model>
+    where
+        I: Iterator<Item = String>,
+    {
+        let instrs = parse_lines(lines, parse_instrs)?;
+        debug!(instructions = instrs.len(), "parsed");
+        Ok(instrs)
+    }
+
+    fn part1(instrs: &Self::Model, _: &NoParams) -> anyhow::Result<i32> {
+        let mut cpu = Cpu::new();
+        let (_, signal_strength) = cpu.run(instrs.iter().cloned())?;
+        Ok(signal_strength)
+    }
+
+    fn part2(instrs: &Self::Model, _: &NoParams) -> anyhow::Result<String> {
+        let mut cpu = Cpu::new();
+        cpu.run(instrs.iter().cloned())?;
+        Ok(cpu.screen())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let mut lines = EXAMPLE.lines();
+        let instrs = parse_instrs(lines.next().unwrap())?;
+        assert_eq!(instrs, Instr::Addx(15));
+
+        let e = parse_instrs("addx 1x").unwrap_err();
+        assert_eq!((e.kind, e.column), (ParseErrorKind::InvalidNumber, 6));
+        let e = parse_instrs("mulx 2").unwrap_err();
+        assert_eq!((e.kind, e.column), (ParseErrorKind::Unexpected, 1));
+        assert!(parse_instrs("addx").is_err());
+        assert!(parse_instrs("").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_run() -> anyhow::Result<()> {
+        let lines = EXAMPLE
+            .lines()
+            .map(|l| parse_instrs(l).expect("Failed to parse"));
+
+        let mut cpu = Cpu::new();
+
+        let (_, signal_strength) = cpu.run(lines)?;
+
+        assert_eq!(signal_strength, 13140);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_screen() -> anyhow::Result<()> {
+        let answers = Day10::solve(EXAMPLE)?;
+        assert_eq!(answers.part2, EXAMPLE_SCREEN);
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+//! Random monkey notes, and a slow but obviously correct solver to check them against.
+
+use common::Answers;
+use rand::{seq::SliceRandom, Rng};
+
+const PRIMES: [i64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+#[derive(Clone, Copy)]
+enum Op {
+    Add(i64),
+    Mul(i64),
+    Double,
+    Square,
+}
+
+impl Op {
+    fn apply(self, old: i64) -> Option<i64> {
+        match self {
+            Op::Add(x) => old.checked_add(x),
+            Op::Mul(x) => old.checked_mul(x),
+            Op::Double => old.checked_add(old),
+            Op::Square => old.checked_mul(old),
+        }
+    }
+}
+
+struct Monkey {
+    items: Vec<i64>,
+    op: Op,
+    divisible_by: i64,
+    if_true: usize,
+    if_false: usize,
+}
+
+fn other<R: Rng + ?Sized>(rng: &mut R, count: usize, not: usize) -> usize {
+    (rng.random_range(1..count) + not) % count
+}
+
+fn monkeys<R: Rng + ?Sized>(rng: &mut R, size: usize) -> Vec<Monkey> {
+    let count = rng.random_range(2..=PRIMES.len());
+    let mut divisors = PRIMES.to_vec();
+    divisors.shuffle(rng);
+    (0..count)
+        .map(|i| Monkey {
+            items: (0..rng.random_range(0..=size))
+                .map(|_| rng.random_range(1..100))
+                .collect(),
+            op: match rng.random_range(0..4) {
+                0 => Op::Add(rng.random_range(1..10)),
+                1 => Op::Mul(rng.random_range(2..20)),
+                2 => Op::Double,
+                _ => Op::Square,
+            },
+            divisible_by: divisors[i],
+            if_true: other(rng, count, i),
+            if_false: other(rng, count, i),
+        })
+        .collect()
+}
+
+fn notes(monkeys: &[Monkey]) -> String {
+    monkeys
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            let items = m
+                .items
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let op = match m.op {
+                Op::Add(x) => format!("old + {}", x),
+                Op::Mul(x) => format!("old * {}", x),
+                Op::Double => "old + old".to_string(),
+                Op::Square => "old * old".to_string(),
+            };
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}",
+                i, items, op, m.divisible_by, m.if_true, m.if_false
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Follows each item on its own, round by round, counting the inspections of every monkey it
+/// passes through. Worry levels are kept modulo `modulus` when there is one, and `None` means they
+/// overflowed.
+fn monkey_business(
+    monkeys: &[Monkey],
+    rounds: usize,
+    relief: i64,
+    modulus: Option<i64>,
+) -> Option<usize> {
+    let mut inspected = vec![0; monkeys.len()];
+    for (start, m) in monkeys.iter().enumerate() {
+        for item in &m.items {
+            let (mut at, mut worry) = (start, *item);
+            let mut round = 0;
+            while round < rounds {
+                let monkey = &monkeys[at];
+                inspected[at] += 1;
+                worry = monkey.op.apply(worry)? / relief;
+                if let Some(modulus) = modulus {
+                    worry %= modulus;
+                }
+                let to = if worry % monkey.divisible_by == 0 {
+                    monkey.if_true
+                } else {
+                    monkey.if_false
+                };
+                // A monkey later in the order gets to it this round, an earlier one next round
+                if to < at {
+                    round += 1;
+                }
+                at = to;
+            }
+        }
+    }
+    inspected.sort_by(|a, b| b.cmp(a));
+    Some(inspected[0] * inspected[1])
+}
+
+/// Notes on two or more monkeys holding up to `size` items each, picked so that worry levels
+/// never overflow over the first 20 rounds.
+pub fn input<R: Rng + ?Sized>(rng: &mut R, size: usize) -> String {
+    loop {
+        let monkeys = monkeys(rng, size);
+        if monkey_business(&monkeys, 20, 3, None).is_some() {
+            return notes(&monkeys);
+        }
+    }
+}
+
+pub fn reference(input: &str) -> Answers {
+    let number = |line: &str| line.rsplit(' ').next().unwrap().parse::<i64>().unwrap();
+    let monkeys = input
+        .split("\n\n")
+        .map(|notes| {
+            let lines = notes.lines().collect::<Vec<_>>();
+            let (_, items) = lines[1].split_once(": ").unwrap();
+            let (_, op) = lines[2].split_once("old ").unwrap();
+            Monkey {
+                items: items
+                    .split(", ")
+                    .filter(|i| !i.is_empty())
+                    .map(|i| i.parse().unwrap())
+                    .collect(),
+                op: match op.split_once(' ').unwrap() {
+                    ("+", "old") => Op::Double,
+                    ("*", "old") => Op::Square,
+                    ("+", x) => Op::Add(x.parse().unwrap()),
+                    (_, x) => Op::Mul(x.parse().unwrap()),
+                },
+                divisible_by: number(lines[3]),
+                if_true: number(lines[4]) as usize,
+                if_false: number(lines[5]) as usize,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let modulus = monkeys.iter().map(|m| m.divisible_by).product::<i64>();
+    Answers::new(
+        monkey_business(&monkeys, 20, 3, None).unwrap(),
+        monkey_business(&monkeys, 10000, 1, Some(modulus)).unwrap(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Part 2 plays 10000 rounds, so keep the number of cases down
+    common::test_against_reference!(crate::Day11, 0..6usize, cases = 32);
+}
@@ -0,0 +1,371 @@
+pub mod generate;
+
+use common::{Details, Example, ParseError, ParseErrorKind, Solution, TopK};
+use serde::Deserialize;
+use tracing::{debug, trace};
+pub mod monkey;
+
+/// Parses every monkey's notes, checking that each throws only to monkeys that exist.
+pub fn parse_monkeys<I, S>(lines: I) -> Result<Vec<monkey::Monkey>, ParseError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let lines = lines.into_iter().collect::<Vec<_>>();
+    let lines = lines.iter().map(|l| l.as_ref()).collect::<Vec<_>>();
+    let mut monkeys = Vec::new();
+    // Where each monkey's notes start, to report bad throws against
+    let mut starts = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        if lines[i].trim().starts_with("Monkey") {
+            let notes = &lines[i..lines.len().min(i + 6)];
+            monkeys.push(monkey::Monkey::parse(notes).map_err(|e| e.offset_lines(i))?);
+            starts.push(i);
+            i += 6;
+        } else if lines[i].trim().is_empty() {
+            i += 1;
+        } else {
+            return Err(ParseError::in_line(
+                ParseErrorKind::Expected("\"Monkey N:\""),
+                lines[i],
+                lines[i].trim_start(),
+            )
+            .at_line(i + 1));
+        }
+    }
+
+    if monkeys.is_empty() {
+        return Err(ParseError::new(
+            ParseErrorKind::Expected("\"Monkey N:\""),
+            "",
+        ));
+    }
+
+    for (m, start) in monkeys.iter().zip(starts) {
+        for (offset, target) in [(4, m.test.pass_true), (5, m.test.pass_false)] {
+            if target >= monkeys.len() {
+                let line = lines[start + offset];
+                return Err(ParseError::in_line(
+                    ParseErrorKind::Invalid(format!("there are only {} monkeys", monkeys.len())),
+                    line,
+                    line.trim_start(),
+                )
+                .at_line(start + offset + 1));
+            }
+        }
+    }
+    Ok(monkeys)
+}
+
+pub fn run_operation(op: &monkey::Operation, old: i64) -> i64 {
+    match op {
+        monkey::Operation::Add(x) => old + x,
+        monkey::Operation::Mul(x) => old * x,
+        monkey::Operation::Sqr => old * old,
+    }
+}
+
+/// Plays one round, returning how many items each monkey inspected.
+pub fn run_monkeys(monkeys: &mut [monkey::Monkey], how_worried: i64) -> anyhow::Result<Vec<usize>> {
+    if how_worried < 1 {
+        return Err(anyhow::anyhow!(
+            "Relief has to divide worry levels by at least 1, not {}",
+            how_worried
+        ));
+    }
+    let mut inspected = Vec::new();
+    let all_divisible_by = monkeys
+        .iter_mut()
+        .map(|m| m.test.divisible_by)
+        .reduce(|acc, i| acc * i)
+        .unwrap();
+
+    for monkey_index in 0..monkeys.len() {
+        let monkey = &mut monkeys[monkey_index];
+
+        let v = monkey
+            .items
+            .clone()
+            .into_iter()
+            .map(|item| {
+                let worry_level = run_operation(&monkey.operation, item) / how_worried;
+                let throw_to = if worry_level % monkey.test.divisible_by == 0 {
+                    monkey.test.pass_true
+                } else {
+                    monkey.test.pass_false
+                };
+                // Working modulo the product of the divisors keeps every test's outcome, but only
+                // holds while nothing is divided out of the worry level
+                if how_worried == 1 {
+                    (throw_to, worry_level % all_divisible_by)
+                } else {
+                    (throw_to, worry_level)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        monkey.items.clear();
+        trace!(monkey = monkey_index, inspected = v.len());
+        inspected.push(v.len());
+        for (throw_to, worry_level) in v {
+            monkeys[throw_to].items.push(worry_level);
+        }
+    }
+
+    Ok(inspected)
+}
+
+/// How many items each monkey inspects over `rounds` rounds.
+pub fn inspections(
+    monkeys: &[monkey::Monkey],
+    rounds: usize,
+    how_worried: i64,
+) -> anyhow::Result<Vec<usize>> {
+    let mut monkeys = monkeys.to_vec();
+
+    let mut total = vec![0; monkeys.len()];
+    for round in 1..=rounds {
+        let passes = run_monkeys(&mut monkeys, how_worried)?;
+        total = passes
+            .into_iter()
+            .enumerate()
+            .map(|(i, p)| total[i] + p)
+            .collect();
+        // The rounds the puzzle shows the counts after
+        if round == 1 || round == 20 || round % 1000 == 0 {
+            debug!(round, inspected = ?total);
+        }
+    }
+    Ok(total)
+}
+
+/// The product of the two largest inspection counts after `rounds` rounds.
+pub fn monkey_business(
+    monkeys: &[monkey::Monkey],
+    rounds: usize,
+    how_worried: i64,
+) -> anyhow::Result<usize> {
+    let mut busiest = TopK::new(2);
+    busiest.extend(inspections(monkeys, rounds, how_worried)?);
+    match busiest.into_sorted_vec()[..] {
+        [first, second] => Ok(first * second),
+        _ => Err(anyhow::anyhow!(
+            "Monkey business needs at least two monkeys"
+        )),
+    }
+}
+
+const EXAMPLE: &str = "Monkey 0:
+    Starting items: 79, 98
+    Operation: new = old * 19
+    Test: divisible by 23
+        If true: throw to monkey 2
+        If false: throw to monkey 3
+
+Monkey 1:
+    Starting items: 54, 65, 75, 74
+    Operation: new = old + 6
+    Test: divisible by 19
+        If true: throw to monkey 2
+        If false: throw to monkey 0
+
+Monkey 2:
+    Starting items: 79, 60, 97
+    Operation: new = old * old
+    Test: divisible by 13
+        If true: throw to monkey 1
+        If false: throw to monkey 3
+
+Monkey 3:
+    Starting items: 74
+    Operation: new = old + 3
+    Test: divisible by 17
+        If true: throw to monkey 0
+        If false: throw to monkey 1";
+
+/// The puzzle's settings.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    /// How many rounds part 1 plays
+    pub part1_rounds: usize,
+    /// What part 1 divides worry levels by after each inspection
+    pub relief: i64,
+    /// How many rounds part 2 plays, without any relief
+    pub part2_rounds: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            part1_rounds: 20,
+            relief: 3,
+            part2_rounds: 10000,
+        }
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Model = Vec<monkey::Monkey>;
+    type Params = Params;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "10605", "2713310158")];
+
+    fn parse_lines<I>(lines: I) -> anyhow::Result<Self::Model>
+    where
+        I: Iterator<Item = String>,
+    {
+        let monkeys = parse_monkeys(lines)?;
+        debug!(monkeys = monkeys.len(), "parsed");
+        Ok(monkeys)
+    }
+
+    fn part1(monkeys: &Self::Model, params: &Params) -> anyhow::Result<usize> {
+        monkey_business(monkeys, params.part1_rounds, params.relief)
+    }
+
+    fn part2(monkeys: &Self::Model, params: &Params) -> anyhow::Result<usize> {
+        monkey_business(monkeys, params.part2_rounds, 1)
+    }
+
+    fn details(monkeys: &Self::Model, params: &Params) -> anyhow::Result<Details> {
+        let mut details = Details::new();
+        details.insert(
+            "inspections_part1".to_string(),
+            inspections(monkeys, params.part1_rounds, params.relief)?.into(),
+        );
+        Ok(details)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{monkey::*, parse_monkeys, run_monkeys, Day11, Params, EXAMPLE};
+    use common::{ParseErrorKind, Solution};
+
+    #[test]
+    fn test_parse_operation() -> anyhow::Result<()> {
+        let operations = EXAMPLE
+            .lines()
+            .filter(|l| l.trim().starts_with("Operation:"))
+            .map(Operation::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            operations,
+            [
+                Operation::Mul(19),
+                Operation::Add(6),
+                Operation::Sqr,
+                Operation::Add(3)
+            ]
+        );
+        assert_eq!(
+            Operation::parse("  Operation: new = old + old")?,
+            Operation::Mul(2)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = Operation::parse("  Operation: new = old / 2").unwrap_err();
+        assert_eq!((e.kind, e.column), (ParseErrorKind::Unexpected, 24));
+        let e = Operation::parse("  Operation: new = old * x").unwrap_err();
+        assert_eq!((e.kind, e.column), (ParseErrorKind::InvalidNumber, 26));
+
+        let bad_item = EXAMPLE.replacen("79, 98", "79, x8", 1);
+        let e = parse_monkeys(bad_item.lines()).unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.kind),
+            (2, 25, ParseErrorKind::InvalidNumber)
+        );
+
+        let bad_throw = EXAMPLE.replacen("monkey 0", "monkey 7", 1);
+        let e = parse_monkeys(bad_throw.lines()).unwrap_err();
+        assert_eq!(e.line, 13);
+
+        let truncated = EXAMPLE.lines().take(10).collect::<Vec<_>>().join("\n");
+        let e = parse_monkeys(truncated.lines()).unwrap_err();
+        assert_eq!(e.line, 11);
+    }
+
+    #[test]
+    fn test_parse_test() -> anyhow::Result<()> {
+        let it = EXAMPLE.lines();
+        let mut iter = it.peekable();
+        let mut divisors = Vec::new();
+        loop {
+            let line = iter.peek();
+            if let Some(line_str) = line {
+                if line_str.trim().starts_with("Test") {
+                    let test_lines = iter.by_ref().take(3).collect::<Vec<_>>();
+                    divisors.push(Test::parse(&test_lines)?.divisible_by);
+                } else {
+                    iter.next();
+                }
+            } else {
+                break;
+            }
+        }
+        assert_eq!(divisors, [23, 19, 13, 17]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_monkeys() -> anyhow::Result<()> {
+        let it = EXAMPLE.lines();
+        let mut monkeys = parse_monkeys(it)?;
+
+        let mut total = vec![0; monkeys.len()];
+        for _rounds in 0..10000 {
+            let passes = run_monkeys(&mut monkeys, 1)?;
+            total = passes
+                .into_iter()
+                .enumerate()
+                .map(|(i, p)| total[i] + p)
+                .collect();
+        }
+        let mut monkey_business = total.clone();
+        monkey_business.sort_by(|a, b| b.cmp(a));
+
+        assert_eq!(total, [52166, 47830, 1938, 52013]);
+        assert_eq!(monkey_business[0] * monkey_business[1], 2713310158);
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve() -> anyhow::Result<()> {
+        let answers = Day11::solve(EXAMPLE)?;
+        assert_eq!(answers.part1, "10605");
+        assert_eq!(answers.part2, "2713310158");
+
+        // The puzzle gives the inspection counts after the first round, and after 20 of part 2
+        let short = Params {
+            part1_rounds: 1,
+            part2_rounds: 20,
+            ..Params::default()
+        };
+        let answers = Day11::solve_with(EXAMPLE, &short)?;
+        assert_eq!(
+            answers.details["inspections_part1"].to_string(),
+            "[2,4,3,5]"
+        );
+        assert_eq!(answers.part2, (103 * 99).to_string());
+
+        for relief in [0, -3] {
+            let params = Params {
+                relief,
+                ..Params::default()
+            };
+            let e = Day11::solve_with(EXAMPLE, &params).unwrap_err();
+            assert!(e.to_string().starts_with("Relief has to divide"), "{}", e);
+        }
+        Ok(())
+    }
+}
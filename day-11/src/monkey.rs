@@ -1,3 +1,5 @@
+use common::{parse::parse_number, ParseError, ParseErrorKind};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Operation {
     Add(i64),
@@ -20,124 +22,98 @@ pub struct Monkey {
     pub test: Test,
 }
 
-impl Operation {
-    pub fn parse(line: &str) -> anyhow::Result<Self> {
-        if let Some((oper, oper_str)) = line.split_once(" = ") {
-            if !oper.trim().starts_with("Operation:") {
-                return Err(anyhow::anyhow!("Error parsing {} - not an operation", line));
-            }
+fn expected(what: &'static str, line: &str, text: &str) -> ParseError {
+    ParseError::in_line(ParseErrorKind::Expected(what), line, text)
+}
 
-            let oper_split = oper_str.split_whitespace().collect::<Vec<_>>();
-            if oper_split[0] != "old" {
-                return Err(anyhow::anyhow!(
-                    "Error parsing {} - function not in terms of old",
-                    line
-                ));
-            }
+/// The text of `line` after `label`, which must start it (after indentation).
+fn after_label<'a>(line: &'a str, label: &'static str) -> Result<&'a str, ParseError> {
+    line.trim_start()
+        .strip_prefix(label)
+        .ok_or_else(|| expected(label, line, line.trim_start()))
+}
 
-            match oper_split[1] {
-                "+" => {
-                    if oper_split[2] == "old" {
-                        return Ok(Operation::Add(2));
-                    }
-                    let add = oper_split[2].parse()?;
-                    Ok(Operation::Add(add))
-                }
-                "*" => {
-                    if oper_split[2] == "old" {
-                        return Ok(Operation::Sqr);
-                    }
-                    let mul = oper_split[2].parse()?;
-                    Ok(Operation::Mul(mul))
-                }
-                _ => Err(anyhow::anyhow!("Error parsing {} - unknown operator", line)),
-            }
-        } else {
-            Err(anyhow::anyhow!("Error parsing {}", line))
+impl Operation {
+    pub fn parse(line: &str) -> Result<Self, ParseError> {
+        let oper_str = after_label(line, "Operation: new = ")?;
+        let oper_split = oper_str.split_whitespace().collect::<Vec<_>>();
+        match oper_split.as_slice() {
+            ["old", "+", "old"] => Ok(Operation::Mul(2)),
+            ["old", "+", add] => Ok(Operation::Add(parse_number(line, add)?)),
+            ["old", "*", "old"] => Ok(Operation::Sqr),
+            ["old", "*", mul] => Ok(Operation::Mul(parse_number(line, mul)?)),
+            ["old", op, _] => Err(ParseError::in_line(ParseErrorKind::Unexpected, line, op)),
+            _ => Err(expected("a function of old", line, oper_str)),
         }
     }
 }
 
 impl Test {
-    pub fn parse(lines: &[&str]) -> anyhow::Result<Self> {
+    /// Parses the three lines of a test. Errors are numbered from the first of them.
+    pub fn parse(lines: &[&str]) -> Result<Self, ParseError> {
         if lines.len() != 3 {
-            return Err(anyhow::anyhow!(
-                "Error parsing {:?} - expected three lines, got {}",
-                lines,
-                lines.len()
-            ));
+            return Err(expected("three lines of test", "", "").at_line(lines.len() + 1));
         }
 
-        let mut t = Test {
-            divisible_by: 0,
-            pass_true: 0,
-            pass_false: 0,
-        };
-
-        if let Some((_, divisible)) = lines[0].split_once(" by ") {
-            t.divisible_by = divisible.parse()?;
-        } else {
-            return Err(anyhow::anyhow!("Error parsing {:?}", lines[0]));
-        }
-
-        if let Some((cond, throw_to)) = lines[1].split_once(" monkey ") {
-            if cond.contains("true:") {
-                t.pass_true = throw_to.parse()?;
-            } else {
-                return Err(anyhow::anyhow!("Error parsing {:?}", lines[1]));
-            }
-        } else {
-            return Err(anyhow::anyhow!("Error parsing {:?}", lines[1]));
+        let divisible = after_label(lines[0], "Test: divisible by ").map_err(|e| e.at_line(1))?;
+        let divisible_by = parse_number(lines[0], divisible).map_err(|e| e.at_line(1))?;
+        if divisible_by <= 0 {
+            return Err(ParseError::in_line(
+                ParseErrorKind::Invalid("divisor must be positive".to_string()),
+                lines[0],
+                divisible,
+            )
+            .at_line(1));
         }
 
-        if let Some((cond, throw_to)) = lines[2].split_once(" monkey ") {
-            if cond.contains("false:") {
-                t.pass_false = throw_to.parse()?;
-            } else {
-                return Err(anyhow::anyhow!("Error parsing {:?}", lines[2]));
-            }
-        } else {
-            return Err(anyhow::anyhow!("Error parsing {:?}", lines[2]));
-        }
+        let throw_to = |i: usize, label| {
+            let line = lines[i];
+            after_label(line, label)
+                .and_then(|target| parse_number(line, target))
+                .map_err(|e| e.at_line(i + 1))
+        };
 
-        Ok(t)
+        Ok(Test {
+            divisible_by,
+            pass_true: throw_to(1, "If true: throw to monkey ")?,
+            pass_false: throw_to(2, "If false: throw to monkey ")?,
+        })
     }
 }
 
 impl Monkey {
-    pub fn parse(lines: &[&str]) -> anyhow::Result<Monkey> {
+    /// Parses the six lines of notes on one monkey. Errors are numbered from the first of them.
+    pub fn parse(lines: &[&str]) -> Result<Monkey, ParseError> {
+        if lines.len() != 6 {
+            return Err(expected("six lines of notes", "", "").at_line(lines.len() + 1));
+        }
+
         let id: i64 = {
-            if !lines[0].trim().starts_with("Monkey") {
-                return Err(anyhow::anyhow!("Error parsing {} - no monkey", lines[0]));
-            }
-            if let Some((_, id)) = lines[0].split_once(' ') {
-                if let Some(id_num) = id.strip_suffix(':') {
-                    id_num.parse()?
-                } else {
-                    return Err(anyhow::anyhow!("Error parsing {}", lines[0]));
-                }
-            } else {
-                return Err(anyhow::anyhow!("Error parsing {}", lines[0]));
-            }
+            let id = after_label(lines[0], "Monkey ")
+                .and_then(|id| {
+                    id.strip_suffix(':')
+                        .ok_or_else(|| expected("':'", lines[0], &id[id.len()..]))
+                })
+                .map_err(|e| e.at_line(1))?;
+            parse_number(lines[0], id).map_err(|e| e.at_line(1))?
         };
 
         let items: Vec<i64> = {
-            if !lines[1].trim().starts_with("Starting items") {
-                return Err(anyhow::anyhow!("Error parsing {} - no items", lines[1]));
-            }
-            if let Some((_, items)) = lines[1].split_once(':') {
+            let items = after_label(lines[1], "Starting items:").map_err(|e| e.at_line(2))?;
+            if items.trim().is_empty() {
+                Vec::new()
+            } else {
                 items
                     .split(',')
-                    .map(|i| i.trim().parse().unwrap())
-                    .collect::<Vec<_>>()
-            } else {
-                return Err(anyhow::anyhow!("Error parsing {}", lines[0]));
+                    .map(|i| parse_number(lines[1], i.trim()))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| e.at_line(2))?
             }
         };
 
-        let operation = Operation::parse(lines[2])?;
+        let operation = Operation::parse(lines[2]).map_err(|e| e.at_line(3))?;
 
-        let test = Test::parse(&lines[3..6])?;
+        let test = Test::parse(&lines[3..6]).map_err(|e| e.offset_lines(3))?;
 
         Ok(Monkey {
             id,
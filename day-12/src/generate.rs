@@ -0,0 +1,126 @@
+//! Random heightmaps, and a slow but obviously correct solver to check them against.
+
+use common::Answers;
+use rand::{
+    seq::{IndexedRandom, SliceRandom},
+    Rng,
+};
+use std::collections::VecDeque;
+
+fn neighbours(p: (usize, usize), width: usize, height: usize) -> Vec<(usize, usize)> {
+    let (x, y) = p;
+    let mut neighbours = Vec::new();
+    if x > 0 {
+        neighbours.push((x - 1, y));
+    }
+    if x + 1 < width {
+        neighbours.push((x + 1, y));
+    }
+    if y > 0 {
+        neighbours.push((x, y - 1));
+    }
+    if y + 1 < height {
+        neighbours.push((x, y + 1));
+    }
+    neighbours
+}
+
+/// A heightmap with sides of up to about 30 plus `size`, where the end can be reached from the
+/// start.
+///
+/// Heights spread out from the end, mostly falling away from it but never by more than one
+/// between a square and the one it was reached from, so every square can climb back to the end.
+pub fn input<R: Rng + ?Sized>(rng: &mut R, size: usize) -> String {
+    loop {
+        let width = rng.random_range(30..=30 + size);
+        let height = rng.random_range(1..=1 + size);
+        let end = (rng.random_range(0..width), rng.random_range(0..height));
+
+        let mut heights = vec![vec![None; width]; height];
+        heights[end.1][end.0] = Some(25);
+        // Squares that could be the start, as the next step from them is no higher than 'b'
+        let mut starts = Vec::new();
+        let mut queue = VecDeque::from([end]);
+        while let Some(p) = queue.pop_front() {
+            let h = heights[p.1][p.0].unwrap();
+            let mut next = neighbours(p, width, height);
+            next.shuffle(rng);
+            for (x, y) in next {
+                if heights[y][x].is_none() {
+                    let below = h.max(1) - 1;
+                    heights[y][x] = Some(if rng.random_bool(0.75) {
+                        below
+                    } else {
+                        rng.random_range(below..=(h + 1).min(25))
+                    });
+                    if h <= 1 {
+                        starts.push((x, y));
+                    }
+                    queue.push_back((x, y));
+                }
+            }
+        }
+
+        if let Some(start) = starts.choose(rng) {
+            return (0..height)
+                .map(|y| {
+                    (0..width)
+                        .map(|x| match (x, y) {
+                            p if p == *start => 'S',
+                            p if p == end => 'E',
+                            _ => (b'a' + heights[y][x].unwrap()) as char,
+                        })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+    }
+}
+
+/// Walks backwards from the end, finding the distance to every square that can reach it.
+pub fn reference(input: &str) -> Answers {
+    let rows = input.lines().map(|l| l.as_bytes()).collect::<Vec<_>>();
+    let (width, height) = (rows[0].len(), rows.len());
+    let elevation = |(x, y): (usize, usize)| match rows[y][x] {
+        b'S' => 0,
+        b'E' => 25,
+        c => c - b'a',
+    };
+    let find = |c| {
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .find(|(x, y)| rows[*y][*x] == c)
+            .unwrap()
+    };
+
+    let mut distance = vec![vec![None; width]; height];
+    let end = find(b'E');
+    distance[end.1][end.0] = Some(0);
+    let mut queue = VecDeque::from([end]);
+    while let Some(p) = queue.pop_front() {
+        let d = distance[p.1][p.0].unwrap();
+        for (x, y) in neighbours(p, width, height) {
+            if distance[y][x].is_none() && elevation(p) <= elevation((x, y)) + 1 {
+                distance[y][x] = Some(d + 1);
+                queue.push_back((x, y));
+            }
+        }
+    }
+
+    let start = find(b'S');
+    let from_lowest = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .filter(|p| elevation(*p) == 0)
+        .filter_map(|(x, y)| distance[y][x])
+        .min()
+        .unwrap();
+    Answers::new(distance[start.1][start.0].unwrap(), from_lowest)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    common::test_against_reference!(crate::Day12, 0..20usize);
+}
@@ -0,0 +1,162 @@
+pub mod generate;
+pub mod visualise;
+
+use common::{search, Example, Grid, NoParams, ParseError, ParseErrorKind, Solution, Vec2};
+use tracing::debug;
+
+/// A square of the heightmap.
+#[derive(Clone, Debug)]
+pub enum NodeType {
+    Start,
+    End,
+    Step(usize),
+}
+
+impl NodeType {
+    /// The square's elevation, from 0 for 'a' to 25 for 'z'.
+    pub fn height(&self) -> usize {
+        match self {
+            NodeType::Start => 0,
+            NodeType::End => 25,
+            NodeType::Step(h) => *h,
+        }
+    }
+}
+
+/// The heightmap, with where to start and where to get to.
+pub struct Map {
+    pub nodes: Grid<NodeType>,
+    pub start: Vec2,
+    pub end: Vec2,
+}
+
+impl Map {
+    pub fn parse(input: &str) -> Result<Map, ParseError> {
+        Self::parse_lines(input.lines())
+    }
+
+    pub fn parse_lines<I, S>(lines: I) -> Result<Map, ParseError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let nodes = Grid::parse_lines(lines, |c| match c {
+            'S' => Some(NodeType::Start),
+            'E' => Some(NodeType::End),
+            i if i.is_ascii_lowercase() => Some(NodeType::Step(i as usize - 'a' as usize)),
+            _ => None,
+        })?;
+
+        let find = |node_type: fn(&NodeType) -> bool, what| {
+            nodes
+                .iter()
+                .find(|(_, n)| node_type(n))
+                .map(|(p, _)| p)
+                .ok_or_else(|| ParseError::new(ParseErrorKind::Expected(what), ""))
+        };
+        let start = find(|n| matches!(n, NodeType::Start), "a start 'S'")?;
+        let end = find(|n| matches!(n, NodeType::End), "an end 'E'")?;
+
+        Ok(Map { nodes, start, end })
+    }
+}
+
+/// The squares next to `p` that are at most one higher, so can be stepped to.
+pub fn climbable(map: &Map, p: Vec2) -> Vec<Vec2> {
+    let height = map.nodes[p].height();
+    map.nodes
+        .neighbours4(p)
+        .filter(|(_, n)| n.height() <= height + 1)
+        .map(|(n, _)| n)
+        .collect()
+}
+
+/// Every lowest square, any of which part 2 can start from.
+pub fn lowest(map: &Map) -> Vec<Vec2> {
+    map.nodes
+        .iter()
+        .filter(|(_, n)| n.height() == 0)
+        .map(|(p, _)| p)
+        .collect()
+}
+
+/// Finds a shortest route from any of `starts` to the end, not including the start itself.
+pub fn find_path(map: &Map, starts: Vec<Vec2>) -> anyhow::Result<Vec<Vec2>> {
+    let starting_squares = starts.len();
+    let result = search::bfs(starts, |p| climbable(map, *p), |p| *p == map.end);
+
+    let path = result
+        .path()
+        .ok_or_else(|| anyhow::anyhow!("Didn't reach the end"))?;
+    debug!(
+        starting_squares,
+        reached = result.distances().len(),
+        from = %path[0],
+        steps = path.len() - 1,
+        "found a route"
+    );
+    Ok(path[1..].to_vec())
+}
+
+const EXAMPLE: &str = "Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi";
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Model = Map;
+    type Params = NoParams;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "31", "29")];
+
+    fn parse_lines<I>(lines: I) -> anyhow::Result<Map>
+    where
+        I: Iterator<Item = String>,
+    {
+        let map = Map::parse_lines(lines)?;
+        debug!(size = %map.nodes.size(), start = %map.start, end = %map.end, "parsed");
+        Ok(map)
+    }
+
+    fn part1(map: &Map, _: &NoParams) -> anyhow::Result<usize> {
+        Ok(find_path(map, vec![map.start])?.len())
+    }
+
+    fn part2(map: &Map, _: &NoParams) -> anyhow::Result<usize> {
+        Ok(find_path(map, lowest(map))?.len())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let map = Map::parse(EXAMPLE)?;
+        let initial_set = vec![map.start];
+        assert_eq!(find_path(&map, initial_set)?.len(), 31);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = Map::parse("Sab\naBc\nxyE").err().unwrap();
+        assert_eq!(e.to_string(), "line 2, column 2: unexpected \"B\"");
+        let e = Map::parse("Sab\nabc").err().unwrap();
+        assert_eq!(e.to_string(), "expected an end 'E', found nothing");
+    }
+
+    #[test]
+    fn test_solve() -> anyhow::Result<()> {
+        let answers = Day12::solve(EXAMPLE)?;
+        assert_eq!(answers.part1, "31");
+        assert_eq!(answers.part2, "29");
+        Ok(())
+    }
+}
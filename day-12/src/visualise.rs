@@ -0,0 +1,156 @@
+use crate::{climbable, find_path, lowest, Day12, Map, NodeType};
+use common::{
+    render::{Colour, Frame, Glyph, Part, Player, Visualise},
+    search, NoParams, Vec2,
+};
+use std::{collections::HashSet, io::Write};
+
+/// The heightmap as the puzzle draws it, coloured by how far the search has got: the squares
+/// reached so far, the ones reached last, and finally the route found.
+pub struct SearchFrame<'a> {
+    pub map: &'a Map,
+    pub reached: &'a HashSet<Vec2>,
+    pub frontier: &'a HashSet<Vec2>,
+    pub path: &'a [Vec2],
+    pub focus: Option<Vec2>,
+    pub caption: String,
+}
+
+impl Frame for SearchFrame<'_> {
+    fn bounds(&self) -> (Vec2, Vec2) {
+        let nodes = &self.map.nodes;
+        (
+            nodes.origin(),
+            nodes.origin() + nodes.size() - Vec2::new(1, 1),
+        )
+    }
+
+    fn glyph(&self, p: Vec2) -> Glyph {
+        let Some(node) = self.map.nodes.get(p) else {
+            return Glyph::BLANK;
+        };
+        let ch = match node {
+            NodeType::Start => 'S',
+            NodeType::End => 'E',
+            NodeType::Step(h) => (b'a' + *h as u8) as char,
+        };
+        let colour = if self.path.contains(&p) {
+            Colour::Green
+        } else if self.frontier.contains(&p) {
+            Colour::Yellow
+        } else if self.reached.contains(&p) {
+            Colour::Blue
+        } else {
+            Colour::Grey
+        };
+        Glyph::coloured(ch, colour)
+    }
+
+    fn focus(&self) -> Option<Vec2> {
+        self.focus
+    }
+
+    fn caption(&self) -> String {
+        self.caption.clone()
+    }
+}
+
+/// The middle of `squares`, to follow the search with.
+fn middle(squares: &HashSet<Vec2>) -> Option<Vec2> {
+    let n = squares.len() as isize;
+    if n == 0 {
+        return None;
+    }
+    let sum = squares.iter().fold(Vec2::origin(), |acc, p| acc + *p);
+    Some(Vec2::new(sum.x / n, sum.y / n))
+}
+
+impl Visualise for Day12 {
+    /// Shows the search spreading out a step at a time until it reaches the end, then the
+    /// route it found.
+    fn visualise<W: Write>(
+        map: &Map,
+        _: &NoParams,
+        part: Part,
+        player: &mut Player<W>,
+    ) -> anyhow::Result<()> {
+        let starts = match part {
+            Part::One => vec![map.start],
+            Part::Two => lowest(map),
+        };
+
+        let mut reached = HashSet::new();
+        let layers = search::bfs_layers(starts.clone(), |p| climbable(map, *p));
+        for (distance, layer) in layers.enumerate() {
+            let frontier = layer.into_iter().collect::<HashSet<_>>();
+            reached.extend(frontier.iter().copied());
+            player.show(&SearchFrame {
+                map,
+                reached: &reached,
+                frontier: &frontier,
+                path: &[],
+                focus: middle(&frontier),
+                caption: format!("Distance {}: {} squares reached", distance, reached.len()),
+            })?;
+            if frontier.contains(&map.end) {
+                break;
+            }
+        }
+
+        let path = find_path(map, starts)?;
+        player.show(&SearchFrame {
+            map,
+            reached: &reached,
+            frontier: &HashSet::new(),
+            path: &path,
+            focus: Some(map.end),
+            caption: format!("Reached the end in {} steps", path.len()),
+        })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::EXAMPLE;
+    use common::render::Viewport;
+
+    #[test]
+    fn test_visualise() -> anyhow::Result<()> {
+        let map = Map::parse(EXAMPLE)?;
+        let mut out = Vec::new();
+        let mut player = Player::new(&mut out, Viewport::default(), 0, false);
+        Day12::visualise(&map, &NoParams {}, Part::One, &mut player)?;
+
+        // A frame for each distance up to the end's, then the route
+        assert_eq!(player.shown(), 31 + 2);
+        let out = String::from_utf8(out)?;
+        assert!(out.contains("Distance 31: "));
+        assert!(out.contains("Reached the end in 31 steps"));
+        assert!(out.contains("Sabqponm"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_colours() -> anyhow::Result<()> {
+        let map = Map::parse(EXAMPLE)?;
+        let reached = HashSet::from([map.start, Vec2::new(1, 0)]);
+        let frontier = HashSet::from([Vec2::new(1, 0)]);
+        let frame = SearchFrame {
+            map: &map,
+            reached: &reached,
+            frontier: &frontier,
+            path: &[],
+            focus: None,
+            caption: String::new(),
+        };
+        assert_eq!(frame.glyph(map.start), Glyph::coloured('S', Colour::Blue));
+        assert_eq!(
+            frame.glyph(Vec2::new(1, 0)),
+            Glyph::coloured('a', Colour::Yellow)
+        );
+        assert_eq!(frame.glyph(map.end), Glyph::coloured('E', Colour::Grey));
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+//! Random packets, and a slow but obviously correct solver to check them against.
+
+use common::Answers;
+use rand::Rng;
+use std::cmp::Ordering;
+
+#[derive(Clone, PartialEq, Eq)]
+enum Packet {
+    Int(u32),
+    List(Vec<Packet>),
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Int(l), Packet::Int(r)) => l.cmp(r),
+            (Packet::List(l), Packet::List(r)) => l.cmp(r),
+            (Packet::Int(_), Packet::List(r)) => std::slice::from_ref(self).cmp(r.as_slice()),
+            (Packet::List(l), Packet::Int(_)) => l.as_slice().cmp(std::slice::from_ref(other)),
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::fmt::Display for Packet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Packet::Int(i) => write!(f, "{}", i),
+            Packet::List(l) => {
+                let items = l.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+                write!(f, "[{}]", items.join(","))
+            }
+        }
+    }
+}
+
+impl Packet {
+    fn parse(line: &str) -> Packet {
+        fn parse_from(chars: &[u8], at: &mut usize) -> Packet {
+            if chars[*at] == b'[' {
+                *at += 1;
+                let mut items = Vec::new();
+                while chars[*at] != b']' {
+                    items.push(parse_from(chars, at));
+                    if chars[*at] == b',' {
+                        *at += 1;
+                    }
+                }
+                *at += 1;
+                Packet::List(items)
+            } else {
+                let digits = chars[*at..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_digit())
+                    .count();
+                let n = std::str::from_utf8(&chars[*at..*at + digits]).unwrap();
+                *at += digits;
+                Packet::Int(n.parse().unwrap())
+            }
+        }
+        parse_from(line.as_bytes(), &mut 0)
+    }
+
+    fn divider(n: u32) -> Packet {
+        Packet::List(vec![Packet::List(vec![Packet::Int(n)])])
+    }
+}
+
+fn list<R: Rng + ?Sized>(rng: &mut R, depth: usize) -> Packet {
+    Packet::List(
+        (0..rng.random_range(0..=4))
+            .map(|_| {
+                if depth > 0 && rng.random_bool(0.3) {
+                    list(rng, depth - 1)
+                } else {
+                    Packet::Int(rng.random_range(0..=10))
+                }
+            })
+            .collect(),
+    )
+}
+
+/// A packet that compares equal to neither divider, so where the dividers sort to is settled.
+fn packet<R: Rng + ?Sized>(rng: &mut R) -> Packet {
+    loop {
+        let packet = list(rng, 3);
+        if [2, 6]
+            .iter()
+            .all(|n| packet.cmp(&Packet::divider(*n)).is_ne())
+        {
+            return packet;
+        }
+    }
+}
+
+/// `size` pairs of packets, none of them equal to the other in its pair.
+pub fn input<R: Rng + ?Sized>(rng: &mut R, size: usize) -> String {
+    (0..size)
+        .map(|_| loop {
+            let (left, right) = (packet(rng), packet(rng));
+            if left.cmp(&right).is_ne() {
+                return format!("{}\n{}", left, right);
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Compares recursively, and places the dividers by counting the packets that come before them.
+pub fn reference(input: &str) -> Answers {
+    let packets = input
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(Packet::parse)
+        .collect::<Vec<_>>();
+    let in_order = packets
+        .chunks(2)
+        .enumerate()
+        .filter(|(_, pair)| pair[0] < pair[1])
+        .map(|(i, _)| i + 1)
+        .sum::<usize>();
+    let before = |divider: Packet| packets.iter().filter(|p| **p < divider).count();
+    Answers::new(
+        in_order,
+        (before(Packet::divider(2)) + 1) * (before(Packet::divider(6)) + 2),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    common::test_against_reference!(crate::Day13, 0..30usize);
+}
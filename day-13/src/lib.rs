@@ -0,0 +1,451 @@
+pub mod generate;
+
+use common::{Details, Example, NoParams, ParseError, ParseErrorKind, Solution};
+use std::cmp::Ordering;
+use tracing::{debug, trace};
+
+struct Arena<T> {
+    items: Vec<T>,
+}
+
+#[derive(Copy, Clone)]
+struct Index {
+    i: usize,
+}
+
+impl<T> Arena<T> {
+    fn new() -> Self {
+        Arena { items: Vec::new() }
+    }
+
+    fn get(&self, id: Index) -> &T {
+        &self.items[id.i]
+    }
+
+    fn get_mut(&mut self, id: Index) -> &mut T {
+        &mut self.items[id.i]
+    }
+
+    fn insert(&mut self, item: T) -> Index {
+        self.items.push(item);
+        Index {
+            i: self.items.len() - 1,
+        }
+    }
+}
+
+#[derive(Clone)]
+enum Node {
+    Int(i32),
+    List(Vec<Index>),
+}
+
+fn append_to(idx: Index, list_idx: Index, arena: &mut Arena<Node>) -> anyhow::Result<()> {
+    let node = arena.get_mut(list_idx);
+
+    match node {
+        Node::List(l) => {
+            l.push(idx);
+        }
+        _ => {
+            return Err(anyhow::anyhow!("Cannot append to non-list node"));
+        }
+    }
+    Ok(())
+}
+
+/// An error about the `len` characters at `idx`.
+fn error_at(kind: ParseErrorKind, chars: &[char], idx: usize, len: usize) -> ParseError {
+    ParseError {
+        column: idx + 1,
+        ..ParseError::new(kind, &chars[idx..idx + len].iter().collect::<String>())
+    }
+}
+
+fn get_number(chars: &[char], idx: usize) -> Result<(i32, usize), ParseError> {
+    let mut next = idx;
+    let mut str = "".to_string();
+    while let Some(c) = chars.get(next).filter(|c| c.is_ascii_digit()) {
+        str.push(*c);
+        next += 1;
+    }
+    let n = str
+        .parse()
+        .map_err(|_| error_at(ParseErrorKind::InvalidNumber, chars, idx, next - idx))?;
+    Ok((n, next))
+}
+
+fn parse(line: &str, arena: &mut Arena<Node>) -> Result<Index, ParseError> {
+    let chars = line.chars().collect::<Vec<_>>();
+    let mut chars_idx = 0;
+    let mut list_stack: Vec<Index> = Vec::new();
+    let mut root_index = None;
+    let append = |idx, list_idx, arena: &mut Arena<Node>, chars_idx| {
+        append_to(idx, list_idx, arena)
+            .map_err(|e| error_at(ParseErrorKind::Invalid(e.to_string()), &chars, chars_idx, 1))
+    };
+    while chars_idx < chars.len() {
+        let c = chars[chars_idx];
+        if root_index.is_some() && list_stack.is_empty() {
+            // Anything after the outermost list has closed
+            return Err(error_at(ParseErrorKind::Unexpected, &chars, chars_idx, 1));
+        }
+        match c {
+            '[' => {
+                let list = Vec::new();
+                let new_idx = arena.insert(Node::List(list));
+
+                if let Some(list_idx) = list_stack.last() {
+                    append(new_idx, *list_idx, arena, chars_idx)?;
+                }
+
+                list_stack.push(new_idx);
+                if root_index.is_none() {
+                    root_index = Some(new_idx);
+                }
+                chars_idx += 1;
+            }
+            ']' if !list_stack.is_empty() => {
+                chars_idx += 1;
+                list_stack.pop();
+            }
+            ',' if !list_stack.is_empty() => {
+                chars_idx += 1;
+            }
+            i if i.is_ascii_digit() && !list_stack.is_empty() => {
+                let (d, new_char_idx) = get_number(&chars, chars_idx)?;
+                let new_node = Node::Int(d);
+                let new_idx = arena.insert(new_node);
+                let list_idx = *list_stack.last().unwrap();
+                append(new_idx, list_idx, arena, chars_idx)?;
+                chars_idx = new_char_idx;
+            }
+            _ if list_stack.is_empty() => {
+                return Err(error_at(
+                    ParseErrorKind::Expected("'['"),
+                    &chars,
+                    chars_idx,
+                    1,
+                ));
+            }
+            _ => {
+                return Err(error_at(ParseErrorKind::Unexpected, &chars, chars_idx, 1));
+            }
+        }
+    }
+
+    match root_index {
+        Some(_) if !list_stack.is_empty() => Err(ParseError::in_line(
+            ParseErrorKind::Expected("']'"),
+            line,
+            &line[line.len()..],
+        )),
+        Some(i) => Ok(i),
+        None => Err(ParseError::in_line(
+            ParseErrorKind::Expected("'['"),
+            line,
+            line,
+        )),
+    }
+}
+
+fn promote(idx: Index, arena: &mut Arena<Node>) -> Index {
+    let list = vec![idx];
+    arena.insert(Node::List(list))
+}
+
+fn compare(root1: Index, root2: Index, arena: &mut Arena<Node>) -> anyhow::Result<Ordering> {
+    let idx_l = arena.get(root1).clone();
+    let idx_r = arena.get(root2).clone();
+    match (idx_l, idx_r) {
+        (Node::List(lhs), Node::List(rhs)) => {
+            let lhs_len = lhs.len();
+            let rhs_len = rhs.len();
+            let zipped = lhs.into_iter().zip(rhs).collect::<Vec<_>>();
+            for (l, r) in zipped {
+                let result = compare(l, r, arena)?;
+                if result != Ordering::Equal {
+                    return Ok(result);
+                }
+            }
+            Ok(lhs_len.cmp(&rhs_len))
+        }
+
+        (Node::Int(_), Node::List(_)) => {
+            let promoted = promote(root1, arena);
+            compare(promoted, root2, arena)
+        }
+
+        (Node::List(_), Node::Int(_)) => {
+            let promoted = promote(root2, arena);
+            compare(root1, promoted, arena)
+        }
+
+        (Node::Int(lhs), Node::Int(rhs)) => Ok(lhs.cmp(&rhs)),
+    }
+}
+
+/// Compares two packets by the distress signal's ordering rules.
+pub fn compare_lines(lhs: &str, rhs: &str) -> anyhow::Result<Ordering> {
+    let mut arena = Arena::new();
+    let root1 = parse(lhs, &mut arena)?;
+    let root2 = parse(rhs, &mut arena)?;
+    let result = compare(root1, root2, &mut arena)?;
+    Ok(result)
+}
+
+/// The sum of the (1-based) indices of the pairs that are already in order.
+pub fn score(packets: &[String]) -> anyhow::Result<usize> {
+    let mut pair = 0;
+    let mut score = 0;
+    for lines in packets.chunks(2) {
+        pair += 1;
+        let (line1, line2) = match lines {
+            [line1, line2] => (line1, line2),
+            _ => return Err(anyhow::anyhow!("Unpaired packet {}", lines[0])),
+        };
+        let result = compare_lines(line1, line2)?;
+        trace!(pair, ?result);
+        match result {
+            Ordering::Less => {
+                score += pair;
+            }
+            Ordering::Equal => {
+                return Err(anyhow::anyhow!("Non-ordered pair {} vs {}", line1, line2));
+            }
+            Ordering::Greater => {}
+        }
+    }
+    Ok(score)
+}
+
+/// Where the divider packets end up (counting from 1) once they're sorted in with the others.
+pub fn divider_indices(packets: &[String]) -> Vec<usize> {
+    let mut lines = packets.iter().map(|l| l.as_str()).collect::<Vec<_>>();
+    lines.push("[[2]]");
+    lines.push("[[6]]");
+    // Every packet was checked when parsing, so comparing them can't fail
+    lines.sort_by(|lhs, rhs| compare_lines(lhs, rhs).unwrap());
+
+    let indices = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| *s == &"[[2]]" || *s == &"[[6]]")
+        .map(|(l, _)| l + 1)
+        .collect();
+    debug!(?indices, "dividers");
+    indices
+}
+
+const EXAMPLE: &str = "[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]";
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    /// Every packet, in input order
+    type Model = Vec<String>;
+    type Params = NoParams;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "13", "140")];
+
+    fn parse_lines<I>(lines: I) -> anyhow::Result<Self::Model>
+    where
+        I: Iterator<Item = String>,
+    {
+        let mut packets = Vec::new();
+        for (i, line) in lines.enumerate() {
+            if !line.is_empty() {
+                parse(&line, &mut Arena::new()).map_err(|e| e.at_line(i + 1))?;
+                packets.push(line);
+            }
+        }
+        debug!(packets = packets.len(), "parsed");
+        Ok(packets)
+    }
+
+    fn part1(packets: &Self::Model, _: &NoParams) -> anyhow::Result<usize> {
+        score(packets)
+    }
+
+    fn part2(packets: &Self::Model, _: &NoParams) -> anyhow::Result<usize> {
+        Ok(divider_indices(packets).into_iter().product())
+    }
+
+    fn details(packets: &Self::Model, _: &NoParams) -> anyhow::Result<Details> {
+        let mut details = Details::new();
+        details.insert(
+            "divider_indices".to_string(),
+            divider_indices(packets).into(),
+        );
+        Ok(details)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Writes the packet at `idx` back out as it would be written in the input.
+    fn walk(arena: &Arena<Node>, idx: Index) -> String {
+        match arena.get(idx) {
+            Node::Int(x) => x.to_string(),
+            Node::List(l) => {
+                let items = l.iter().map(|i| walk(arena, *i)).collect::<Vec<_>>();
+                format!("[{}]", items.join(","))
+            }
+        }
+    }
+
+    #[test]
+    fn test_arena() {
+        let mut arena: Arena<Node> = Arena::new();
+
+        let mut l = Vec::new();
+        l.push(arena.insert(Node::Int(1)));
+        let l2 = vec![arena.insert(Node::Int(2))];
+        l.push(arena.insert(Node::List(l2)));
+        l.push(arena.insert(Node::Int(3)));
+        let _id = arena.insert(Node::List(l));
+    }
+
+    #[test]
+    fn test_parse() -> anyhow::Result<()> {
+        let mut arena: Arena<Node> = Arena::new();
+        let packet = "[1,[2,3,[4]],5,[6,7],8,9,10]";
+        let idx = parse(packet, &mut arena)?;
+        assert_eq!(walk(&arena, idx), packet);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let mut arena: Arena<Node> = Arena::new();
+        let e = parse("[1,[2,x]]", &mut arena).err().unwrap();
+        assert_eq!((e.kind, e.column), (ParseErrorKind::Unexpected, 7));
+        let e = parse("[1,[2]", &mut arena).err().unwrap();
+        assert_eq!((e.kind, e.column), (ParseErrorKind::Expected("']'"), 7));
+        let e = parse("1,[2]", &mut arena).err().unwrap();
+        assert_eq!((e.kind, e.column), (ParseErrorKind::Expected("'['"), 1));
+        let e = parse("[1]]", &mut arena).err().unwrap();
+        assert_eq!((e.kind, e.column), (ParseErrorKind::Unexpected, 4));
+        let e = parse("[99999999999]", &mut arena).err().unwrap();
+        assert_eq!((e.kind, e.column), (ParseErrorKind::InvalidNumber, 2));
+        assert!(parse("", &mut arena).is_err());
+
+        let e = Day13::parse("[1]\n[2]\n\n[3]\n[4").err().unwrap();
+        assert_eq!(
+            e.to_string(),
+            "line 5, column 3: expected ']', found nothing"
+        );
+    }
+
+    #[test]
+    fn test_basic_compare() -> anyhow::Result<()> {
+        let mut arena: Arena<Node> = Arena::new();
+        let idx = parse("[1,2,3,4]", &mut arena)?;
+        let idx2 = parse("[1,2,3,4,5]", &mut arena)?;
+        let result = compare(idx, idx2, &mut arena)?;
+        assert_eq!(result, Ordering::Less);
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty_compare() -> anyhow::Result<()> {
+        let mut arena: Arena<Node> = Arena::new();
+        let idx = parse("[[[]]]", &mut arena)?;
+        let idx2 = parse("[[]]", &mut arena)?;
+        let result = compare(idx, idx2, &mut arena)?;
+        assert_eq!(result, Ordering::Greater);
+        Ok(())
+    }
+
+    #[test]
+    fn test_promote_compare() -> anyhow::Result<()> {
+        let mut arena: Arena<Node> = Arena::new();
+        let idx = parse("[1,2,3]", &mut arena)?;
+        let idx2 = parse("[1,2,[4]]", &mut arena)?;
+        let result = compare(idx, idx2, &mut arena)?;
+        assert_eq!(result, Ordering::Less);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_samples() -> anyhow::Result<()> {
+        let test_cases = vec![
+            // ("[1,1,3,1,1]", "[1,1,5,1,1]", Ordering::Less),
+            // ("[[1],[2,3,4]]", "[[1],4]", Ordering::Less),
+            ("[9]", "[[8,7,6]]", Ordering::Greater),
+            // ("[[4,4],4,4]", "[[4,4],4,4,4]", Ordering::Less),
+            // ("[7,7,7,7]", "[7,7,7]", Ordering::Greater),
+            // ("[]", "[3]", Ordering::Less),
+            // ("[[[]]]", "[[]]", Ordering::Greater),
+            // (
+            //     "[1,[2,[3,[4,[5,6,7]]]],8,9]",
+            //     "[1,[2,[3,[4,[5,6,0]]]],8,9]",
+            //     Ordering::Greater,
+            // ),
+        ];
+
+        for (lhs, rhs, expected_result) in test_cases {
+            let mut arena: Arena<Node> = Arena::new();
+            let idx = parse(lhs, &mut arena)?;
+            let idx2 = parse(rhs, &mut arena)?;
+            let result = compare(idx, idx2, &mut arena)?;
+            assert_eq!(
+                result, expected_result,
+                "{} {} {:?}",
+                lhs, rhs, expected_result
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_score() -> anyhow::Result<()> {
+        assert_eq!(score(&Day13::parse(EXAMPLE)?)?, 13);
+        Ok(())
+    }
+
+    #[test]
+    fn failing_test() -> anyhow::Result<()> {
+        let line1 = "[[[[1,8,3,7],9,7,[],[6,3,5,2,9]],3,4,[],[9]]]";
+        let line2 = "[[[[1],8,6,[]],9],[[7,[2,8,0,9]],[[4,2,5,5],5],0],[3,[[3,1,8],10,[],0,5],6,[]],[[9,[2,8,0,0,1],[],[1,1,8]],[9,9,[2,9,1,1,1],4,2],[[1],8,[0,5,6,7,8]],[7,7,[4,6,10,10],[4,0,9]],3]]";
+        let mut arena: Arena<Node> = Arena::new();
+        let idx = parse(line1, &mut arena)?;
+        let idx2 = parse(line2, &mut arena)?;
+        let result = compare(idx, idx2, &mut arena)?;
+        assert_eq!(result, Ordering::Greater);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort() {
+        let packets = Day13::parse(EXAMPLE).unwrap();
+        assert_eq!(Day13::part2(&packets, &NoParams {}).unwrap(), 140);
+        assert_eq!(divider_indices(&packets), vec![10, 14]);
+    }
+}
@@ -1,8 +1,7 @@
 use core::fmt;
-use std::fmt::Write;
 
-use crate::vec2::Vec2;
 use anyhow::{anyhow, Result};
+use common::{Grid, Vec2};
 use itertools::Itertools;
 
 #[derive(Clone, Copy, Eq, PartialEq, Debug)]
@@ -12,45 +11,45 @@ pub enum Cell {
     Sand,
 }
 
+/// The cave slice, covering the corners it was made with.
 pub struct Field {
-    origin: Vec2,
-    size: Vec2,
-    cells: Vec<Cell>,
+    cells: Grid<Cell>,
 }
 
 impl Field {
     pub fn new(top_left: Vec2, bottom_right: Vec2) -> Self {
-        let size = bottom_right.sub(&top_left).add(&Vec2::new(1, 1));
         Field {
-            origin: top_left,
-            size,
-            cells: vec![Cell::Empty; (size.x * size.y) as usize],
+            cells: Grid::from_corners(top_left, bottom_right, Cell::Empty),
         }
     }
 
-    fn cell_offset(&self, p: Vec2) -> Result<usize> {
-        let v = Vec2::new(p.x - self.origin.x, p.y - self.origin.y);
-        if v.x >= 0 && v.x < self.size.x && v.y >= 0 && v.y < self.size.y {
-            Ok((v.x + v.y * self.size.x) as usize)
-        } else {
-            Err(anyhow!("Cell out of bounds"))
-        }
+    pub fn cells(&self) -> &Grid<Cell> {
+        &self.cells
+    }
+
+    /// The top-left and bottom-right corners.
+    pub fn corners(&self) -> (Vec2, Vec2) {
+        let origin = self.cells.origin();
+        (origin, origin + self.cells.size() - Vec2::new(1, 1))
     }
 
     pub fn in_bounds(&self, p: Vec2) -> bool {
-        p.x >= self.origin.x
-            && p.x < self.origin.x + self.size.x
-            && p.y >= self.origin.y
-            && p.y < self.origin.y + self.size.y
+        self.cells.in_bounds(p)
     }
 
     pub fn get(&self, p: Vec2) -> Result<Cell> {
-        Ok(self.cells[self.cell_offset(p)?])
+        self.cells
+            .get(p)
+            .copied()
+            .ok_or_else(|| anyhow!("Cell out of bounds"))
     }
 
     pub fn put(&mut self, p: Vec2, c: Cell) -> Result<()> {
-        let offset = self.cell_offset(p)?;
-        self.cells[offset] = c;
+        let cell = self
+            .cells
+            .get_mut(p)
+            .ok_or_else(|| anyhow!("Cell out of bounds"))?;
+        *cell = c;
         Ok(())
     }
 
@@ -63,7 +62,7 @@ impl Field {
                 if pos == *second {
                     break;
                 }
-                pos = pos.add(&dir);
+                pos += dir;
             }
         }
         Ok(())
@@ -72,18 +71,11 @@ impl Field {
 
 impl fmt::Display for Field {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for y in 0..self.size.y {
-            for x in 0..self.size.x {
-                let c = x + y * self.size.x;
-                f.write_char(match self.cells[c as usize] {
-                    Cell::Empty => '.',
-                    Cell::Rock => '#',
-                    Cell::Sand => 'o',
-                })?;
-            }
-            f.write_str("\n")?;
-        }
-
-        Ok(())
+        let cells = self.cells.display_with(|c| match c {
+            Cell::Empty => '.',
+            Cell::Rock => '#',
+            Cell::Sand => 'o',
+        });
+        f.write_fmt(format_args!("{}", cells))
     }
 }
@@ -0,0 +1,85 @@
+//! Random rock scans, and a slow but obviously correct solver to check them against.
+
+use common::Answers;
+use rand::Rng;
+use std::collections::HashSet;
+
+/// Between one and `size` rock paths, each of a few straight lines, scattered within `size` of
+/// the source and below it.
+pub fn input<R: Rng + ?Sized>(rng: &mut R, size: usize) -> String {
+    let size = size as i64;
+    (0..rng.random_range(1..=size.max(1)))
+        .map(|_| {
+            let mut x = rng.random_range(500 - size..=500 + size);
+            let mut y = rng.random_range(1..=size + 1);
+            let mut points = vec![format!("{},{}", x, y)];
+            for _ in 0..rng.random_range(1..=3) {
+                if rng.random_bool(0.5) {
+                    x += rng.random_range(-5..=5);
+                } else {
+                    y = (y + rng.random_range(-5..=5)).max(1);
+                }
+                points.push(format!("{},{}", x, y));
+            }
+            points.join(" -> ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Drops sand a grain at a time into a set of blocked points, with or without the floor.
+fn grains(rocks: &HashSet<(isize, isize)>, floor: bool) -> usize {
+    let lowest = rocks.iter().map(|(_, y)| *y).max().unwrap_or(0);
+    let mut blocked = rocks.clone();
+    let mut grains = 0;
+    while !blocked.contains(&(500, 0)) {
+        let (mut x, mut y) = (500, 0);
+        loop {
+            if !floor && y > lowest {
+                return grains;
+            }
+            if floor && y == lowest + 1 {
+                break;
+            }
+            match [x, x - 1, x + 1]
+                .into_iter()
+                .find(|x| !blocked.contains(&(*x, y + 1)))
+            {
+                Some(next) => (x, y) = (next, y + 1),
+                None => break,
+            }
+        }
+        blocked.insert((x, y));
+        grains += 1;
+    }
+    grains
+}
+
+pub fn reference(input: &str) -> Answers {
+    let mut rocks = HashSet::new();
+    for line in input.lines() {
+        let points = line
+            .split(" -> ")
+            .map(|p| {
+                let (x, y) = p.split_once(',').unwrap();
+                (x.parse::<isize>().unwrap(), y.parse::<isize>().unwrap())
+            })
+            .collect::<Vec<_>>();
+        for pair in points.windows(2) {
+            let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+            for x in x1.min(x2)..=x1.max(x2) {
+                for y in y1.min(y2)..=y1.max(y2) {
+                    rocks.insert((x, y));
+                }
+            }
+        }
+    }
+    Answers::new(grains(&rocks, false), grains(&rocks, true))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    common::test_against_reference!(crate::Day14, 0..30usize);
+}
@@ -0,0 +1,303 @@
+pub mod generate;
+pub mod visualise;
+
+pub mod field;
+
+use anyhow::{anyhow, Result};
+use common::{parse::parse_lines, vec2::Vec2, Example, ParseError, Solution};
+use field::{Cell, Field};
+use serde::Deserialize;
+use tracing::{debug, trace};
+
+use std::cmp::{max, min};
+
+/// Parses a rock path of the form "x,y -> x,y -> ...".
+pub fn parse_line(l: &str) -> Result<Vec<Vec2>, ParseError> {
+    l.split("->")
+        .map(|s| s.trim().parse::<Vec2>().map_err(|e| e.within(l, s.trim())))
+        .collect()
+}
+
+/// The top-left and bottom-right corners of the box around `v`.
+pub fn bounds(v: &[Vec2]) -> Result<(Vec2, Vec2)> {
+    let mut itr = v.iter();
+    let first = itr.next();
+    if first.is_none() {
+        return Err(anyhow!("Cannot get bounds for no Vec2s"));
+    }
+    let (mut top_left, mut bottom_right) = (*first.unwrap(), *first.unwrap());
+
+    for v in itr {
+        top_left.x = min(top_left.x, v.x);
+        top_left.y = min(top_left.y, v.y);
+        bottom_right.x = max(bottom_right.x, v.x);
+        bottom_right.y = max(bottom_right.y, v.y);
+    }
+
+    Ok((top_left, bottom_right))
+}
+
+/// Where a unit of sand ended up.
+#[derive(PartialEq, Eq, Debug)]
+pub enum DropResult {
+    Rested(Vec2),
+    OutOfBounds,
+    Full,
+}
+
+/// Drops a unit of sand from `start` until it comes to rest or falls out of the field.
+pub fn drop_sand(field: &mut Field, start: Vec2) -> Result<DropResult> {
+    if field.get(start)? != Cell::Empty {
+        return Ok(DropResult::Full);
+    }
+    let mut pos = start;
+
+    let to_check = [Vec2::new(0, 1), Vec2::new(-1, 1), Vec2::new(1, 1)];
+
+    loop {
+        let mut new_pos = pos;
+
+        for p in to_check.into_iter().map(|d| pos + d) {
+            if !field.in_bounds(p) {
+                return Ok(DropResult::OutOfBounds);
+            }
+            let c = field.get(p)?;
+            if c == Cell::Empty {
+                new_pos = p;
+                break;
+            }
+        }
+
+        if pos != new_pos {
+            pos = new_pos;
+        } else {
+            break;
+        }
+    }
+
+    field.put(pos, Cell::Sand)?;
+
+    Ok(DropResult::Rested(pos))
+}
+
+/// Draws the rock paths into a field big enough for them and the sand's `source`, plus a floor
+/// `floor` below the lowest rock if there is one.
+pub fn build_field(paths: &[Vec<Vec2>], source: Vec2, floor: Option<isize>) -> Result<Field> {
+    let mut lines = paths.to_vec();
+
+    // The source has to be in the field too, even if every rock is off to one side
+    let mut points = lines.clone().into_iter().flatten().collect::<Vec<_>>();
+    points.push(source);
+    let (mut top_left, mut bottom_right) = bounds(&points)?;
+
+    if let Some(floor) = floor {
+        // Sand piles up no wider than a 45 degree slope from the source to the floor
+        bottom_right.y += floor;
+        let spread = bottom_right.y - source.y;
+        top_left.x = min(top_left.x, source.x - spread);
+        bottom_right.x = max(bottom_right.x, source.x + spread);
+        lines.push(vec![
+            Vec2::new(top_left.x, bottom_right.y),
+            Vec2::new(bottom_right.x, bottom_right.y),
+        ]);
+    }
+
+    debug!(%top_left, %bottom_right, "field");
+    let mut field = Field::new(top_left, bottom_right);
+
+    for line in lines {
+        field.draw_lines(&line)?;
+    }
+
+    Ok(field)
+}
+
+const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
+
+/// The puzzle's settings.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    /// Where the sand pours in from, as x and y
+    pub source: (isize, isize),
+    /// How far below the lowest rock part 2's floor is
+    pub floor: isize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            source: (500, 0),
+            floor: 2,
+        }
+    }
+}
+
+impl Params {
+    pub fn source(&self) -> Vec2 {
+        Vec2::new(self.source.0, self.source.1)
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    /// Each rock path, as the points it is drawn through
+    type Model = Vec<Vec<Vec2>>;
+    type Params = Params;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "24", "93")];
+
+    fn parse_lines<I>(lines: I) -> Result<Self::Model>
+    where
+        I: Iterator<Item = String>,
+    {
+        let paths = parse_lines(lines, parse_line)?;
+        debug!(paths = paths.len(), "parsed");
+        Ok(paths)
+    }
+
+    fn part1(paths: &Self::Model, params: &Params) -> Result<usize> {
+        let mut field = build_field(paths, params.source(), None)?;
+
+        let mut i = 0;
+        loop {
+            i += 1;
+            // Rocks can form a basin that fills right up to the source without spilling
+            let r = drop_sand(&mut field, params.source())?;
+            trace!(grain = i, ?r);
+            if !matches!(r, DropResult::Rested(_)) {
+                break;
+            }
+        }
+
+        Ok(i - 1)
+    }
+
+    fn part2(paths: &Self::Model, params: &Params) -> Result<usize> {
+        if params.floor < 1 {
+            return Err(anyhow!("The floor must be below the lowest rock"));
+        }
+        let mut field = build_field(paths, params.source(), Some(params.floor))?;
+
+        let mut i = 0;
+        loop {
+            i += 1;
+            let r = drop_sand(&mut field, params.source())?;
+            trace!(grain = i, ?r);
+            match r {
+                DropResult::Rested(_) => {}
+                DropResult::OutOfBounds => {
+                    debug!("sand fell out of the field:\n{}", field);
+                    return Err(anyhow!("Should never get out of bounds"));
+                }
+                DropResult::Full => break,
+            }
+        }
+
+        Ok(i - 1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test() -> Result<()> {
+        let mut l = EXAMPLE.lines();
+        let first = l.next().unwrap();
+        assert_eq!(
+            parse_line(first)?,
+            vec![Vec2::new(498, 4), Vec2::new(498, 6), Vec2::new(496, 6)]
+        );
+
+        let e = parse_line("498,4 -> 498,x -> 496,6").unwrap_err();
+        assert_eq!(e.column, 14);
+        let e = Day14::parse("498,4 -> 498,6\n498,4 -> 498 6").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 10: expected x,y, found \"498 6\""
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_draw_lines() -> Result<()> {
+        let l = "498,4 -> 498,6 -> 496,6";
+        let points = parse_line(l)?;
+        let (top_left, bottom_right) = bounds(&points)?;
+        let mut f = Field::new(top_left, bottom_right);
+
+        f.draw_lines(&parse_line(l)?)?;
+        assert_eq!(f.get(Vec2::new(498, 4))?, Cell::Rock);
+        assert_eq!(f.get(Vec2::new(498, 5))?, Cell::Rock);
+        assert_eq!(f.get(Vec2::new(498, 6))?, Cell::Rock);
+        assert_eq!(f.get(Vec2::new(497, 6))?, Cell::Rock);
+        assert_eq!(f.get(Vec2::new(496, 6))?, Cell::Rock);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_drop() -> Result<()> {
+        let mut field = build_field(&Day14::parse(EXAMPLE)?, Vec2::new(500, 0), None)?;
+
+        assert_eq!(
+            drop_sand(&mut field, Vec2::new(500, 0))?,
+            DropResult::Rested(Vec2::new(500, 8))
+        );
+        drop_sand(&mut field, Vec2::new(500, 0))?;
+        drop_sand(&mut field, Vec2::new(500, 0))?;
+        drop_sand(&mut field, Vec2::new(500, 0))?;
+        drop_sand(&mut field, Vec2::new(500, 0))?;
+
+        assert_eq!(field.get(Vec2::new(500, 8))?, Cell::Sand);
+        assert_eq!(field.get(Vec2::new(499, 8))?, Cell::Sand);
+        assert_eq!(field.get(Vec2::new(501, 8))?, Cell::Sand);
+        assert_eq!(field.get(Vec2::new(500, 7))?, Cell::Sand);
+        assert_eq!(field.get(Vec2::new(498, 8))?, Cell::Sand);
+        Ok(())
+    }
+
+    #[test]
+    fn test_drop_lots() -> Result<()> {
+        let mut field = build_field(&Day14::parse(EXAMPLE)?, Vec2::new(500, 0), Some(2))?;
+        for i in 0..100 {
+            let r = drop_sand(&mut field, Vec2::new(500, 0))?;
+            match r {
+                DropResult::Rested(_) => assert!(i < 93),
+                DropResult::OutOfBounds => unreachable!(),
+                DropResult::Full => assert!(i >= 93),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve() -> Result<()> {
+        let answers = Day14::solve(EXAMPLE)?;
+        assert_eq!(answers.part1, "24");
+        assert_eq!(answers.part2, "93");
+
+        // Moving the source one to the left is the same as moving every rock one to the right
+        let moved = Params {
+            source: (499, 0),
+            ..Params::default()
+        };
+        let shifted = "499,4 -> 499,6 -> 497,6\n504,4 -> 503,4 -> 503,9 -> 495,9";
+        assert_eq!(Day14::solve_with(EXAMPLE, &moved)?, Day14::solve(shifted)?);
+
+        let deeper = Params {
+            floor: 3,
+            ..Params::default()
+        };
+        assert_eq!(Day14::solve_with(EXAMPLE, &deeper)?.part2, "111");
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{
+    build_field, drop_sand,
+    field::{Cell, Field},
+    Day14, DropResult, Params,
+};
+use anyhow::anyhow;
+use common::{
+    image::{Draw, Image, Picture, Rgb},
+    render::{Colour, Frame, Glyph, Part, Player, Visualise},
+    Vec2,
+};
+use std::io::Write;
+
+/// The cave slice with the source marked and the grain that came to rest last picked out.
+pub struct SandFrame<'a> {
+    pub field: &'a Field,
+    pub source: Vec2,
+    pub grain: Option<Vec2>,
+    pub caption: String,
+}
+
+impl Frame for SandFrame<'_> {
+    fn bounds(&self) -> (Vec2, Vec2) {
+        self.field.corners()
+    }
+
+    fn glyph(&self, p: Vec2) -> Glyph {
+        if Some(p) == self.grain {
+            return Glyph::coloured('o', Colour::Red);
+        }
+        match self.field.get(p) {
+            _ if p == self.source => Glyph::coloured('+', Colour::Green),
+            Ok(Cell::Empty) => Glyph::coloured('.', Colour::Grey),
+            Ok(Cell::Rock) => Glyph::coloured('#', Colour::White),
+            Ok(Cell::Sand) => Glyph::coloured('o', Colour::Yellow),
+            Err(_) => Glyph::BLANK,
+        }
+    }
+
+    fn focus(&self) -> Option<Vec2> {
+        self.grain.or(Some(self.source))
+    }
+
+    fn caption(&self) -> String {
+        self.caption.clone()
+    }
+}
+
+/// Builds the field for `part` and checks its parameters, as the solution does.
+fn field_for(paths: &[Vec<Vec2>], params: &Params, part: Part) -> anyhow::Result<Field> {
+    let floor = match part {
+        Part::One => None,
+        Part::Two if params.floor < 1 => {
+            return Err(anyhow!("The floor must be below the lowest rock"))
+        }
+        Part::Two => Some(params.floor),
+    };
+    build_field(paths, params.source(), floor)
+}
+
+impl Visualise for Day14 {
+    /// Shows the cave each time a grain of sand comes to rest.
+    fn visualise<W: Write>(
+        paths: &Self::Model,
+        params: &Params,
+        part: Part,
+        player: &mut Player<W>,
+    ) -> anyhow::Result<()> {
+        let source = params.source();
+        let mut field = field_for(paths, params, part)?;
+
+        let mut grains = 0;
+        let mut grain = None;
+        loop {
+            player.show(&SandFrame {
+                field: &field,
+                source,
+                grain,
+                caption: format!("{} grains of sand at rest", grains),
+            })?;
+            match drop_sand(&mut field, source)? {
+                DropResult::Rested(p) => {
+                    grains += 1;
+                    grain = Some(p);
+                }
+                DropResult::OutOfBounds if part == Part::Two => {
+                    return Err(anyhow!("Should never get out of bounds"))
+                }
+                DropResult::OutOfBounds | DropResult::Full => break,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Draw for Day14 {
+    /// Draws the cave once the sand has stopped, a pixel per position.
+    fn draw(paths: &Self::Model, params: &Params, part: Part) -> anyhow::Result<Picture> {
+        let source = params.source();
+        let mut field = field_for(paths, params, part)?;
+        while let DropResult::Rested(_) = drop_sand(&mut field, source)? {}
+
+        let mut image = Image::from_grid(field.cells(), |c| match c {
+            Cell::Empty => Rgb::BLACK,
+            Cell::Rock => Colour::Grey.into(),
+            Cell::Sand => Colour::Yellow.into(),
+        });
+        let source = source - field.corners().0;
+        image.set(source.x as usize, source.y as usize, Colour::Green.into());
+        Ok(Picture::Raster(image))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::EXAMPLE;
+    use common::{render::Viewport, Solution};
+
+    #[test]
+    fn test_visualise() -> anyhow::Result<()> {
+        let paths = Day14::parse(EXAMPLE)?;
+        let mut out = Vec::new();
+        let mut player = Player::new(&mut out, Viewport::default(), 0, false);
+        Day14::visualise(&paths, &Params::default(), Part::One, &mut player)?;
+
+        // The empty cave, then one frame per grain
+        assert_eq!(player.shown(), 25);
+        let out = String::from_utf8(out)?;
+        let last = out.rsplit("24 grains of sand at rest").next().unwrap();
+        assert!(last.contains("..+..."));
+        assert!(last.contains("#########."));
+        Ok(())
+    }
+
+    #[test]
+    fn test_draw() -> anyhow::Result<()> {
+        let paths = Day14::parse(EXAMPLE)?;
+        let Picture::Raster(image) = Day14::draw(&paths, &Params::default(), Part::Two)? else {
+            panic!("expected pixels");
+        };
+        // The floor is 11 down, and the sand spreads as far either way from the source
+        assert_eq!((image.width(), image.height()), (23, 12));
+        let sand = Rgb::from(Colour::Yellow);
+        let pixels = (0..image.height()).flat_map(|y| (0..image.width()).map(move |x| (x, y)));
+        assert_eq!(
+            pixels
+                .filter(|(x, y)| image.get(*x, *y) == Some(sand))
+                .count(),
+            92
+        );
+        assert_eq!(image.get(11, 0), Some(Colour::Green.into()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_cropped() -> anyhow::Result<()> {
+        let paths = Day14::parse(EXAMPLE)?;
+        let field = build_field(&paths, Vec2::new(500, 0), Some(2))?;
+        let frame = SandFrame {
+            field: &field,
+            source: Vec2::new(500, 0),
+            grain: None,
+            caption: String::new(),
+        };
+        // Too narrow for the floor, so the source stays in the middle
+        let top = Viewport::new(5, 1).render(&frame, false);
+        assert_eq!(top, "..+..\n");
+        Ok(())
+    }
+}
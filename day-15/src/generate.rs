@@ -0,0 +1,112 @@
+//! Random sensor reports, and a slow but obviously correct solver to check them against.
+//!
+//! The real puzzle asks about row 2000000 and a 4000000 square, far too big to check cell by
+//! cell, so the reference takes the row and the square's size to use instead.
+
+use common::vec2::Vec2;
+use rand::Rng;
+
+/// Up to `size` sensors and a few beacons, all within `size` of the origin. Each sensor's
+/// beacon is strictly the closest one to it, as the puzzle promises.
+pub fn input<R: Rng + ?Sized>(rng: &mut R, size: usize) -> String {
+    let size = size.max(1) as i64;
+    let point = |rng: &mut R| {
+        Vec2::new(
+            rng.random_range(0..=size) as isize,
+            rng.random_range(0..=size) as isize,
+        )
+    };
+    let beacons = (0..rng.random_range(1..=3))
+        .map(|_| point(rng))
+        .collect::<Vec<_>>();
+
+    let mut lines = Vec::new();
+    for _ in 0..rng.random_range(1..=size) {
+        let sensor = point(rng);
+        let mut distances = beacons
+            .iter()
+            .map(|b| (sensor.manhattan(b), *b))
+            .collect::<Vec<_>>();
+        distances.sort_by_key(|(d, _)| *d);
+        let (distance, beacon) = distances[0];
+        let tied = distances.get(1).is_some_and(|(d, _)| *d == distance);
+        if distance == 0 || tied {
+            continue;
+        }
+        lines.push(format!(
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+            sensor.x, sensor.y, beacon.x, beacon.y
+        ));
+    }
+    // Make sure there's at least one report
+    if lines.is_empty() {
+        let b = beacons[0];
+        lines.push(format!(
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+            b.x,
+            b.y - 1,
+            b.x,
+            b.y
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Checks every cell: how many in row `y` can't hold a beacon, and the first cell, row by row,
+/// in the square from (0, 0) to (max, max) that no sensor covers.
+pub fn reference(input: &str, y: isize, max: isize) -> (usize, Option<Vec2>) {
+    let sensors = input
+        .lines()
+        .map(|line| {
+            let numbers = line
+                .split(['=', ',', ':'])
+                .filter_map(|s| s.parse::<isize>().ok())
+                .collect::<Vec<_>>();
+            (
+                Vec2::new(numbers[0], numbers[1]),
+                Vec2::new(numbers[2], numbers[3]),
+            )
+        })
+        .collect::<Vec<_>>();
+    let covered = |p: Vec2| {
+        sensors
+            .iter()
+            .any(|(s, b)| s.manhattan(&p) <= s.manhattan(b))
+    };
+
+    let reach = sensors
+        .iter()
+        .map(|(s, b)| s.x.abs() + s.manhattan(b) as isize)
+        .max()
+        .unwrap_or(0);
+    let invalid = (-reach..=reach)
+        .map(|x| Vec2::new(x, y))
+        .filter(|p| covered(*p) && sensors.iter().all(|(_, b)| b != p))
+        .count();
+    let uncovered = (0..=max)
+        .flat_map(|y| (0..=max).map(move |x| Vec2::new(x, y)))
+        .find(|p| !covered(*p));
+    (invalid, uncovered)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{find_beacon, invalid_spaces_in_line, parse};
+    use proptest::prelude::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    proptest! {
+        #[test]
+        fn test_matches_reference(seed: u64, size in 0..30usize, y in -5..35isize) {
+            let input = input(&mut StdRng::seed_from_u64(seed), size);
+            let sensors = parse(input.lines()).unwrap();
+            let max = size as isize;
+            let answers = (
+                invalid_spaces_in_line(y, &sensors).unwrap(),
+                find_beacon(max, &sensors).ok(),
+            );
+            prop_assert_eq!(answers, reference(&input, y, max));
+        }
+    }
+}
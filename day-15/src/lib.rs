@@ -0,0 +1,306 @@
+pub mod generate;
+pub mod visualise;
+
+use anyhow::{anyhow, Result};
+use common::{
+    parse::parse_number, vec2::Vec2, Example, Interval, IntervalSet, ParseError, ParseErrorKind,
+    Solution,
+};
+use itertools::Itertools;
+use regex::Regex;
+use serde::Deserialize;
+use std::{
+    cmp::{max, min},
+    sync::LazyLock,
+};
+use tracing::debug;
+
+/// A sensor and the closest beacon to it.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Sensor {
+    pub loc: Vec2,
+    pub beacon: Vec2,
+}
+
+impl Sensor {
+    pub fn new(loc: Vec2, beacon: Vec2) -> Self {
+        Sensor { loc, beacon }
+    }
+
+    /// How far the sensor can see: the Manhattan distance to its beacon.
+    pub fn distance(&self) -> usize {
+        self.loc.manhattan(&self.beacon)
+    }
+}
+
+/// A position in a sensor report, compiled once rather than for every line.
+static POSITION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"x=(-?\d+), y=(-?\d+)").unwrap());
+
+pub fn parse_line(line: &str) -> Result<Sensor, ParseError> {
+    let captures = POSITION.captures_iter(line).collect_vec();
+    if captures.len() != 2 {
+        return Err(ParseError::in_line(
+            ParseErrorKind::Expected("a sensor and a beacon position"),
+            line,
+            line,
+        ));
+    }
+    let coord = |i: usize, j: usize| parse_number(line, captures[i].get(j).unwrap().as_str());
+    Ok(Sensor::new(
+        Vec2::new(coord(0, 1)?, coord(0, 2)?),
+        Vec2::new(coord(1, 1)?, coord(1, 2)?),
+    ))
+}
+
+/// Parses one sensor report per line.
+pub fn parse<I, S>(lines: I) -> Result<Vec<Sensor>, ParseError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, l)| parse_line(l.as_ref()).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+/// The leftmost and rightmost x any sensor reaches, taking in x = 0.
+fn lr_boundaries(sensors: &[Sensor]) -> (isize, isize) {
+    let mut mn = 0;
+    let mut mx = 0;
+    for s in sensors {
+        let l = s.loc.x - s.distance() as isize;
+        let r = s.loc.x + s.distance() as isize;
+        mn = min(l, mn);
+        mx = max(r, mx);
+    }
+    (mn, mx)
+}
+
+/// How many positions in row `y` can't hold a beacon: those some sensor covers, apart from
+/// the beacons already there.
+pub fn invalid_spaces_in_line(y: isize, sensors: &[Sensor]) -> Result<usize> {
+    let covered = project_onto(y, sensors);
+    // Every beacon is as far as its sensor reaches, so is always covered
+    let beacons = sensors
+        .iter()
+        .filter(|s| s.beacon.y == y)
+        .map(|s| s.beacon.x)
+        .unique()
+        .count();
+    let count = covered.size() as usize - beacons;
+    debug!(y, covered = covered.size(), beacons, count, "checked row");
+    Ok(count)
+}
+
+/// The x positions the sensors cover in row `y`.
+pub fn project_onto(y: isize, sensors: &[Sensor]) -> IntervalSet<isize> {
+    sensors
+        .iter()
+        .filter_map(|s| {
+            let reach = s.distance().checked_sub((s.loc.y - y).unsigned_abs())? as isize;
+            Some(Interval::new(s.loc.x - reach, s.loc.x + reach))
+        })
+        .collect()
+}
+
+/// Finds the one position in the square from (0, 0) to (max, max) that no sensor covers.
+pub fn find_beacon(max: isize, sensors: &[Sensor]) -> Result<Vec2> {
+    let row = IntervalSet::from(Interval::new(0, max));
+    for y in 0..=max {
+        if let Some(gap) = row.difference(&project_onto(y, sensors)).iter().next() {
+            debug!(x = gap.lower(), y, "found the beacon");
+            return Ok(Vec2::new(gap.lower(), y));
+        }
+    }
+    Err(anyhow!("No uncovered position found"))
+}
+
+const EXAMPLE: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+
+/// The puzzle's settings.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    /// The row part 1 counts in
+    pub row: isize,
+    /// The far corner of the square part 2 searches, from (0, 0)
+    pub max: isize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            row: 2000000,
+            max: 4000000,
+        }
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Model = Vec<Sensor>;
+    type Params = Params;
+    type Part1 = usize;
+    type Part2 = isize;
+
+    // The example asks about row 10 and a 20x20 area rather than the real input's
+    const EXAMPLES: &'static [Example] =
+        &[Example::new(EXAMPLE, "26", "56000011").with_params("row = 10\nmax = 20")];
+
+    fn parse_lines<I>(lines: I) -> Result<Self::Model>
+    where
+        I: Iterator<Item = String>,
+    {
+        let sensors = parse(lines)?;
+        debug!(sensors = sensors.len(), "parsed");
+        Ok(sensors)
+    }
+
+    fn part1(sensors: &Self::Model, params: &Params) -> Result<usize> {
+        invalid_spaces_in_line(params.row, sensors)
+    }
+
+    fn part2(sensors: &Self::Model, params: &Params) -> Result<isize> {
+        let beacon = find_beacon(params.max, sensors)?;
+        Ok(beacon.x * 4000000 + beacon.y)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(PartialEq, Eq, Debug)]
+    enum Cell {
+        Sensor,
+        Beacon,
+        InRange,
+        Empty,
+    }
+
+    fn get_cell(pos: Vec2, sensors: &[Sensor]) -> Cell {
+        // Sensors and beacons take precedence over any other sensor's range
+        if sensors.iter().any(|s| s.loc == pos) {
+            Cell::Sensor
+        } else if sensors.iter().any(|s| s.beacon == pos) {
+            Cell::Beacon
+        } else if sensors
+            .iter()
+            .any(|s| s.loc.manhattan(&pos) <= s.distance())
+        {
+            Cell::InRange
+        } else {
+            Cell::Empty
+        }
+    }
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        let lines = EXAMPLE.lines().collect_vec();
+        let parsed = parse_line(lines[0])?;
+        let expected = Sensor::new(Vec2::new(2, 18), Vec2::new(-2, 15));
+        assert_eq!(parsed, expected);
+
+        let e =
+            parse_line("Sensor at x=2, y=18: closest beacon is at x=-2, y=99999999999999999999")
+                .unwrap_err();
+        assert_eq!((e.kind, e.column), (ParseErrorKind::InvalidNumber, 51));
+        let e = parse("Sensor at x=2, y=18: closest beacon is nowhere".lines()).unwrap_err();
+        assert_eq!((e.line, e.column), (1, 1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty() -> Result<()> {
+        let sensors = parse(EXAMPLE.lines())?;
+        assert_eq!(get_cell(Vec2::new(-3, 10), &sensors), Cell::Empty);
+        assert_eq!(get_cell(Vec2::new(-2, 10), &sensors), Cell::InRange);
+        assert_eq!(get_cell(Vec2::new(24, 10), &sensors), Cell::InRange);
+        assert_eq!(get_cell(Vec2::new(25, 10), &sensors), Cell::Empty);
+
+        assert_eq!(get_cell(Vec2::new(13, 11), &sensors), Cell::InRange);
+        assert_eq!(get_cell(Vec2::new(14, 11), &sensors), Cell::Empty);
+        assert_eq!(get_cell(Vec2::new(15, 11), &sensors), Cell::InRange);
+        Ok(())
+    }
+
+    #[test]
+    fn test_boundaries() -> Result<()> {
+        let sensors = parse(EXAMPLE.lines())?;
+        let (l, r) = lr_boundaries(&sensors);
+        assert_eq!(l, -8);
+        assert_eq!(r, 28);
+        Ok(())
+    }
+
+    #[test]
+    fn test_count() -> Result<()> {
+        let sensors = parse(EXAMPLE.lines())?;
+        assert_eq!(invalid_spaces_in_line(10, &sensors)?, 26);
+
+        // The same as checking every position in the row
+        let (l, r) = lr_boundaries(&sensors);
+        for y in -5..=25 {
+            let brute_force = (l..=r)
+                .filter(|x| {
+                    matches!(
+                        get_cell(Vec2::new(*x, y), &sensors),
+                        Cell::InRange | Cell::Sensor
+                    )
+                })
+                .count();
+            assert_eq!(
+                invalid_spaces_in_line(y, &sensors)?,
+                brute_force,
+                "row {}",
+                y
+            );
+        }
+
+        Ok(())
+    }
+    #[test]
+    fn test_project() -> Result<()> {
+        let test = Sensor::new(Vec2::new(0, 11), Vec2::new(2, 10));
+
+        let r = project_onto(10, &[test]);
+        assert_eq!(r, Interval::new(-2, 2).into());
+
+        // The example's row 11 is covered apart from one gap
+        let sensors = parse(EXAMPLE.lines())?;
+        let r = project_onto(11, &sensors);
+        assert_eq!(r.gaps().collect_vec(), [Interval::single(14)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_beacon() -> Result<()> {
+        let sensors = parse(EXAMPLE.lines())?;
+        assert_eq!(find_beacon(20, &sensors)?, Vec2::new(14, 11));
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve() -> Result<()> {
+        let answers = Day15::solve_with(EXAMPLE, &Params { row: 10, max: 20 })?;
+        assert_eq!(answers.part1, "26");
+        assert_eq!(answers.part2, "56000011");
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{find_beacon, lr_boundaries, Day15, Params};
+use common::{
+    image::{Draw, Picture, Rgb},
+    render::{Colour, Part},
+    svg::{Style, Svg},
+    Vec2,
+};
+
+impl Draw for Day15 {
+    /// Draws each sensor's range as a diamond, with the sensors and their beacons as dots. Part 1
+    /// adds the row it counts in, and part 2 the square it searches and the beacon it finds.
+    fn draw(sensors: &Self::Model, params: &Params, part: Part) -> anyhow::Result<Picture> {
+        let mut svg = Svg::new();
+        svg.background(Rgb::BLACK);
+
+        for s in sensors {
+            let d = s.distance() as isize;
+            let diamond = [
+                s.loc + Vec2::new(0, -d),
+                s.loc + Vec2::new(d, 0),
+                s.loc + Vec2::new(0, d),
+                s.loc + Vec2::new(-d, 0),
+            ];
+            svg.polygon(&diamond, Style::fill(Colour::Blue.into()).opacity(0.3));
+        }
+
+        // Dots big enough to see once the whole picture is scaled down to fit
+        let (l, r) = lr_boundaries(sensors);
+        let radius = ((r - l) as f64 / 300.0).max(0.3);
+        for s in sensors {
+            svg.circle(s.loc, radius, Style::fill(Colour::Red.into()))
+                .circle(s.beacon, radius, Style::fill(Colour::White.into()));
+        }
+
+        match part {
+            Part::One => {
+                let row = [Vec2::new(l, params.row), Vec2::new(r, params.row)];
+                svg.polyline(&row, Style::stroke(Colour::Yellow.into(), 1.0));
+            }
+            Part::Two => {
+                let size = Vec2::new(params.max, params.max);
+                svg.rect(
+                    Vec2::origin(),
+                    size,
+                    Style::stroke(Colour::Green.into(), 1.0),
+                );
+                let beacon = find_beacon(params.max, sensors)?;
+                svg.circle(beacon, 3.0 * radius, Style::fill(Colour::Green.into()));
+            }
+        }
+        Ok(Picture::Vector(svg))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse, EXAMPLE};
+
+    #[test]
+    fn test_draw() -> anyhow::Result<()> {
+        let sensors = parse(EXAMPLE.lines())?;
+        let params = Params { row: 10, max: 20 };
+        let Picture::Vector(svg) = Day15::draw(&sensors, &params, Part::Two)? else {
+            panic!("expected shapes");
+        };
+        let drawn = svg.to_string();
+
+        assert_eq!(drawn.matches("<polygon").count(), sensors.len());
+        // The sensor at (8, 7) reaches its beacon 9 away
+        assert!(drawn.contains(r#"points="8,-2 17,7 8,16 -1,7""#));
+        assert!(drawn.contains(r#"<rect x="0" y="0" width="20" height="20""#));
+        assert!(drawn.contains(r#"<circle cx="14" cy="11""#));
+
+        let Picture::Vector(svg) = Day15::draw(&sensors, &params, Part::One)? else {
+            panic!("expected shapes");
+        };
+        assert!(svg
+            .to_string()
+            .contains(r#"<polyline points="-8,10 28,10""#));
+        Ok(())
+    }
+}
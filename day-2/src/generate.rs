@@ -0,0 +1,51 @@
+//! Random strategy guides, and a slow but obviously correct solver to check them against.
+
+use common::Answers;
+use rand::Rng;
+
+/// A guide of `size` rounds.
+pub fn input<R: Rng + ?Sized>(rng: &mut R, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let them = ['A', 'B', 'C'][rng.random_range(0..3)];
+            let me = ['X', 'Y', 'Z'][rng.random_range(0..3)];
+            format!("{} {}", them, me)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 0, 3 or 6 for losing, drawing or winning with `me` against `them` (0 is rock, 1 paper and 2
+/// scissors, each beating the one before it).
+fn outcome(them: u32, me: u32) -> u32 {
+    if me == them {
+        3
+    } else if me == (them + 1) % 3 {
+        6
+    } else {
+        0
+    }
+}
+
+/// Scores each round directly, and for part 2 tries every move until one gets the result.
+pub fn reference(input: &str) -> Answers {
+    let (mut part1, mut part2) = (0, 0);
+    for line in input.lines() {
+        let bytes = line.as_bytes();
+        let them = (bytes[0] - b'A') as u32;
+        let column = (bytes[2] - b'X') as u32;
+
+        part1 += outcome(them, column) + column + 1;
+
+        let me = (0..3).find(|me| outcome(them, *me) == column * 3).unwrap();
+        part2 += outcome(them, me) + me + 1;
+    }
+    Answers::new(part1, part2)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    common::test_against_reference!(crate::Day2, 0..100usize);
+}
@@ -0,0 +1,276 @@
+pub mod generate;
+pub mod rules;
+
+use anyhow::Result;
+use common::{Example, ParseError, ParseErrorKind, Solution};
+use rules::{Game, Move, Outcome};
+use serde::{de, Deserialize, Deserializer};
+use std::path::PathBuf;
+use tracing::{debug, trace};
+
+/// Splits a line of the guide into its two columns.
+fn columns(line: &str) -> Result<(&str, &str), ParseError> {
+    line.split_once(' ')
+        .ok_or_else(|| ParseError::in_line(ParseErrorKind::Expected("two columns"), line, line))
+}
+
+/// A round of the strategy guide. What the second column means depends on the part, so its
+/// code is kept as written, with where it is for errors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Round {
+    pub them: Move,
+    pub code: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Round {
+    /// An error about the round's code.
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            ..ParseError::new(kind, &self.code)
+        }
+    }
+
+    /// The code read as my move, as part 1 reads it.
+    pub fn my_move(&self, game: &Game) -> Result<Move, ParseError> {
+        game.my_move(&self.code)
+            .ok_or_else(|| self.error(ParseErrorKind::Unexpected))
+    }
+
+    /// The code read as the outcome I need, and the move that gets it, as part 2 reads it.
+    pub fn needed_move(&self, game: &Game) -> Result<(Outcome, Move), ParseError> {
+        let outcome = game
+            .needed_outcome(&self.code)
+            .ok_or_else(|| self.error(ParseErrorKind::Unexpected))?;
+        let me = game.move_for(self.them, outcome).ok_or_else(|| {
+            let why = format!("no move can {} against {}", outcome, game.name(self.them));
+            self.error(ParseErrorKind::Invalid(why))
+        })?;
+        Ok((outcome, me))
+    }
+}
+
+/// Reads a line of the guide as a round of `game`, checking their move. `number` is the line's,
+/// or 0 if it isn't known.
+pub fn round_for_line(game: &Game, number: usize, line: &str) -> Result<Round, ParseError> {
+    let (them, code) = columns(line)?;
+    let them = game
+        .their_move(them)
+        .ok_or_else(|| ParseError::in_line(ParseErrorKind::Unexpected, line, them))?;
+    Ok(Round {
+        them,
+        code: code.to_string(),
+        line: number,
+        column: line[..line.len() - code.len()].chars().count() + 1,
+    })
+}
+
+/// Reads a line of the guide as their move and mine.
+pub fn moves_for_line(game: &Game, line: &str) -> Result<(Move, Move), ParseError> {
+    let round = round_for_line(game, 0, line)?;
+    Ok((round.them, round.my_move(game)?))
+}
+
+/// Reads a line of the guide as their move and the outcome I need.
+pub fn move_and_outcome_for_line(game: &Game, line: &str) -> Result<(Move, Outcome), ParseError> {
+    let round = round_for_line(game, 0, line)?;
+    let (outcome, _) = round.needed_move(game)?;
+    Ok((round.them, outcome))
+}
+
+/// The strategy guide's rounds, and the game they're played in.
+pub struct Guide {
+    pub game: Game,
+    pub rounds: Vec<Round>,
+}
+
+const EXAMPLE: &str = "A Y
+B X
+C Z";
+
+/// The puzzle's settings.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    /// The game from a rules file, such as rules/rpsls.toml, instead of rock, paper, scissors.
+    /// It's read as the parameters are, so solving never goes back to the file.
+    #[serde(rename = "rules", deserialize_with = "load_rules")]
+    pub game: Game,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            game: Game::standard(),
+        }
+    }
+}
+
+fn load_rules<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Game, D::Error> {
+    let path = PathBuf::deserialize(deserializer)?;
+    Game::load(&path).map_err(|e| de::Error::custom(format!("{:#}", e)))
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Model = Guide;
+    type Params = Params;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "15", "12")];
+
+    fn parse_lines<I>(lines: I) -> Result<Guide>
+    where
+        I: Iterator<Item = String>,
+    {
+        Self::parse_lines_with(lines, &Params::default())
+    }
+
+    /// Reads their moves as the game's, so a move the game doesn't have is reported where it
+    /// is. The second column is only read once a part says what it means.
+    fn parse_lines_with<I>(lines: I, params: &Params) -> Result<Guide>
+    where
+        I: Iterator<Item = String>,
+    {
+        let game = params.game.clone();
+        let rounds = lines
+            .enumerate()
+            .map(|(i, line)| round_for_line(&game, i + 1, &line).map_err(|e| e.at_line(i + 1)))
+            .collect::<Result<Vec<_>, _>>()?;
+        debug!(rounds = rounds.len(), "parsed");
+        Ok(Guide { game, rounds })
+    }
+
+    fn part1(guide: &Guide, _: &Params) -> Result<u32> {
+        let game = &guide.game;
+        let mut total = 0;
+        for round in &guide.rounds {
+            let me = round.my_move(game)?;
+            let score = game.score(round.them, me);
+            trace!(them = game.name(round.them), me = game.name(me), score);
+            total += score;
+        }
+        Ok(total)
+    }
+
+    fn part2(guide: &Guide, _: &Params) -> Result<u32> {
+        let game = &guide.game;
+        let mut total = 0;
+        for round in &guide.rounds {
+            let (outcome, me) = round.needed_move(game)?;
+            trace!(them = game.name(round.them), %outcome, me = game.name(me));
+            total += game.score(round.them, me);
+        }
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn names(game: &Game, moves: (Move, Move)) -> (&str, &str) {
+        (game.name(moves.0), game.name(moves.1))
+    }
+
+    #[test]
+    fn check_moves_for_line() {
+        let game = Game::standard();
+        let moves = |line| names(&game, moves_for_line(&game, line).unwrap());
+        assert_eq!(moves("A X"), ("rock", "rock"));
+        assert_eq!(moves("B Y"), ("paper", "paper"));
+        assert_eq!(moves("C Z"), ("scissors", "scissors"));
+    }
+
+    #[test]
+    fn check_test_data_rule1() {
+        let game = Game::standard();
+        let test_data = vec![("A Y", 8), ("B X", 1), ("C Z", 6)];
+
+        for (line, score) in test_data {
+            let (them, me) = moves_for_line(&game, line).unwrap();
+            assert_eq!(game.score(them, me), score);
+        }
+    }
+
+    #[test]
+    fn check_move_and_outcome_for_line() {
+        let game = Game::standard();
+        let read = |line| {
+            let (them, outcome) = move_and_outcome_for_line(&game, line).unwrap();
+            (game.name(them), outcome)
+        };
+        assert_eq!(read("A X"), ("rock", Outcome::Lose));
+        assert_eq!(read("B Y"), ("paper", Outcome::Draw));
+        assert_eq!(read("C Z"), ("scissors", Outcome::Win));
+    }
+
+    #[test]
+    fn check_test_data_rule2() {
+        let game = Game::standard();
+        let test_data = vec![("A Y", 4), ("B X", 1), ("C Z", 7), ("B Z", 9)];
+
+        for (line, score) in test_data {
+            let (them, outcome) = move_and_outcome_for_line(&game, line).unwrap();
+            let me = game.move_for(them, outcome).unwrap();
+            assert_eq!(game.score(them, me), score);
+        }
+    }
+
+    #[test]
+    fn check_bad_lines() -> anyhow::Result<()> {
+        let game = Game::standard();
+        let e = moves_for_line(&game, "A W").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (3, "W"));
+        let e = move_and_outcome_for_line(&game, "D X").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (1, "D"));
+        assert!(moves_for_line(&game, "AX").is_err());
+
+        let e = Day2::parse("A Y\nB X\nD Y").err().unwrap();
+        assert_eq!(e.to_string(), "line 3, column 1: unexpected \"D\"");
+        // The second column is read by each part, and only fails the one that can't read it
+        let guide = Day2::parse("A Y\nB X\nC Q")?;
+        let e = Day2::part1(&guide, &Params::default()).unwrap_err();
+        assert_eq!(e.to_string(), "line 3, column 3: unexpected \"Q\"");
+        let e = Day2::parse("A Y\nBX").err().unwrap();
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 1: expected two columns, found \"BX\""
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve() -> anyhow::Result<()> {
+        let answers = Day2::solve(EXAMPLE)?;
+        assert_eq!(answers.part1, "15");
+        assert_eq!(answers.part2, "12");
+        Ok(())
+    }
+
+    #[test]
+    fn test_rules_file() -> anyhow::Result<()> {
+        let params: Params = toml::from_str("rules = \"rules/rpsls.toml\"")?;
+        // Scissors (3) beats lizard, lizard (4) draws, and spock (5) beats scissors
+        let answers = Day2::solve_with("D X\nD Y\nC Z", &params)?;
+        assert_eq!(answers.part1, (9 + 7 + 11).to_string());
+        // Lizard beats paper, the first listed of the two it beats, and rock is the first
+        // listed to beat scissors
+        assert_eq!(answers.part2, (2 + 7 + 7).to_string());
+
+        // W is a move in this game, but not an outcome, so only part 2 can't read it: rock
+        // loses to scissors (3) and paper (2) beats rock
+        let guide = Day2::parse_with("A X\nA W", &params)?;
+        assert_eq!(Day2::part1(&guide, &params)?, 3 + 2 + 6);
+        let e = Day2::part2(&guide, &params).unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 3: unexpected \"W\"");
+
+        assert!(toml::from_str::<Params>("rules = \"rules/missing.toml\"").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,335 @@
+//! Games of rock-paper-scissors with any number of moves, defined by a rules file.
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+
+/// The puzzle's own game.
+const STANDARD: &str = include_str!("../rules/rps.toml");
+
+/// A move as the rules file describes it.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MoveRule {
+    name: String,
+    /// What the guide's first column calls it
+    them: String,
+    /// What the guide's second column calls it, when that's read as my move
+    me: String,
+    /// What playing it scores
+    score: u32,
+}
+
+/// An outcome as the rules file describes it.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OutcomeRule {
+    /// What the guide's second column calls it, when that's read as the outcome I need
+    code: String,
+    score: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Outcomes {
+    lose: OutcomeRule,
+    draw: OutcomeRule,
+    win: OutcomeRule,
+}
+
+/// A rules file, as written.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Rules {
+    moves: Vec<MoveRule>,
+    /// The moves each move beats. Without it the moves go round in a cycle, each beating the
+    /// half of the others before it, as rock, paper, scissors do.
+    beats: Option<BTreeMap<String, Vec<String>>>,
+    outcomes: Outcomes,
+}
+
+/// One of a game's moves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Move(usize);
+
+/// How a round ends for me, the second player.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Outcome::Lose => "lose",
+            Outcome::Draw => "draw",
+            Outcome::Win => "win",
+        })
+    }
+}
+
+/// A game's moves, which beats which, and what everything scores.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Game {
+    moves: Vec<MoveRule>,
+    /// Whether the move in each row beats the move in each column
+    beats: Vec<Vec<bool>>,
+    outcomes: Outcomes,
+}
+
+impl Game {
+    /// Rock, paper, scissors, as the puzzle plays it.
+    pub fn standard() -> Self {
+        Game::from_toml(STANDARD).expect("the standard rules are valid")
+    }
+
+    /// Reads a rules file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        Game::from_toml(&text).with_context(|| format!("Invalid rules in {}", path.display()))
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self> {
+        Game::new(toml::from_str(text)?)
+    }
+
+    fn new(rules: Rules) -> Result<Self> {
+        let moves = rules.moves;
+        let n = moves.len();
+        if n == 0 {
+            return Err(anyhow!("There are no moves"));
+        }
+        for (i, m) in moves.iter().enumerate() {
+            let clash =
+                |other: &MoveRule| other.name == m.name || other.them == m.them || other.me == m.me;
+            if moves[..i].iter().any(clash) {
+                return Err(anyhow!(
+                    "Move {:?} has the same name or code as another",
+                    m.name
+                ));
+            }
+        }
+        let codes = [
+            &rules.outcomes.lose,
+            &rules.outcomes.draw,
+            &rules.outcomes.win,
+        ];
+        if codes[0].code == codes[1].code
+            || codes[1].code == codes[2].code
+            || codes[0].code == codes[2].code
+        {
+            return Err(anyhow!("The outcomes need different codes"));
+        }
+
+        let mut beats = vec![vec![false; n]; n];
+        match rules.beats {
+            None => {
+                if n.is_multiple_of(2) {
+                    return Err(anyhow!(
+                        "A cycle of {} moves leaves some pairs undecided; it needs an odd number",
+                        n
+                    ));
+                }
+                for (i, row) in beats.iter_mut().enumerate() {
+                    for back in 1..=n / 2 {
+                        row[(i + n - back) % n] = true;
+                    }
+                }
+            }
+            Some(relation) => {
+                let index = |name: &str| {
+                    moves
+                        .iter()
+                        .position(|m| m.name == name)
+                        .ok_or_else(|| anyhow!("There's no move called {:?}", name))
+                };
+                for (winner, losers) in &relation {
+                    let w = index(winner)?;
+                    for loser in losers {
+                        let l = index(loser)?;
+                        if w == l {
+                            return Err(anyhow!("{:?} can't beat itself", winner));
+                        }
+                        beats[w][l] = true;
+                    }
+                }
+                for w in 0..n {
+                    for l in 0..n {
+                        if beats[w][l] && beats[l][w] {
+                            return Err(anyhow!(
+                                "{:?} and {:?} can't both beat each other",
+                                moves[w].name,
+                                moves[l].name
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(Game {
+            moves,
+            beats,
+            outcomes: rules.outcomes,
+        })
+    }
+
+    /// Every move, in the order the rules list them.
+    pub fn moves(&self) -> impl Iterator<Item = Move> {
+        (0..self.moves.len()).map(Move)
+    }
+
+    pub fn name(&self, m: Move) -> &str {
+        &self.moves[m.0].name
+    }
+
+    pub fn beats(&self, a: Move, b: Move) -> bool {
+        self.beats[a.0][b.0]
+    }
+
+    /// How a round where they play `them` and I play `me` ends for me. Moves that neither beat
+    /// draw.
+    pub fn outcome(&self, them: Move, me: Move) -> Outcome {
+        if self.beats(me, them) {
+            Outcome::Win
+        } else if self.beats(them, me) {
+            Outcome::Lose
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    /// A move for me that ends in `outcome` against `them`: the first the rules list, if there's
+    /// more than one, or the same move for a draw.
+    pub fn move_for(&self, them: Move, outcome: Outcome) -> Option<Move> {
+        if outcome == Outcome::Draw {
+            return Some(them);
+        }
+        self.moves().find(|me| self.outcome(them, *me) == outcome)
+    }
+
+    fn outcome_rule(&self, outcome: Outcome) -> &OutcomeRule {
+        match outcome {
+            Outcome::Lose => &self.outcomes.lose,
+            Outcome::Draw => &self.outcomes.draw,
+            Outcome::Win => &self.outcomes.win,
+        }
+    }
+
+    /// What I score for a round: for the shape I played, and for how it ended.
+    pub fn score(&self, them: Move, me: Move) -> u32 {
+        self.moves[me.0].score + self.outcome_rule(self.outcome(them, me)).score
+    }
+
+    /// Their move from its code in the guide's first column.
+    pub fn their_move(&self, code: &str) -> Option<Move> {
+        self.moves().find(|m| self.moves[m.0].them == code)
+    }
+
+    /// My move from its code in the guide's second column.
+    pub fn my_move(&self, code: &str) -> Option<Move> {
+        self.moves().find(|m| self.moves[m.0].me == code)
+    }
+
+    /// The outcome I need from its code in the guide's second column.
+    pub fn needed_outcome(&self, code: &str) -> Option<Outcome> {
+        [Outcome::Lose, Outcome::Draw, Outcome::Win]
+            .into_iter()
+            .find(|o| self.outcome_rule(*o).code == code)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn by_name(game: &Game, name: &str) -> Move {
+        game.moves().find(|m| game.name(*m) == name).unwrap()
+    }
+
+    #[test]
+    fn test_standard() {
+        let game = Game::standard();
+        let [rock, paper, scissors] = ["rock", "paper", "scissors"].map(|n| by_name(&game, n));
+        assert!(game.beats(rock, scissors));
+        assert!(game.beats(paper, rock));
+        assert!(game.beats(scissors, paper));
+        assert!(!game.beats(scissors, rock));
+        assert_eq!(game.outcome(scissors, scissors), Outcome::Draw);
+
+        assert_eq!(game.score(rock, scissors), 3);
+        assert_eq!(game.score(rock, paper), 8);
+        assert_eq!(game.score(rock, rock), 4);
+
+        assert_eq!(game.move_for(rock, Outcome::Lose), Some(scissors));
+        assert_eq!(game.move_for(rock, Outcome::Win), Some(paper));
+        assert_eq!(game.move_for(rock, Outcome::Draw), Some(rock));
+    }
+
+    #[test]
+    fn test_rpsls() -> Result<()> {
+        let explicit = Game::load(Path::new("rules/rpsls.toml"))?;
+        let [rock, paper, scissors, lizard, spock] =
+            ["rock", "paper", "scissors", "lizard", "spock"].map(|n| by_name(&explicit, n));
+        assert!(explicit.beats(lizard, spock));
+        assert!(explicit.beats(spock, scissors));
+        assert_eq!(explicit.outcome(rock, lizard), Outcome::Lose);
+        assert_eq!(explicit.move_for(rock, Outcome::Win), Some(paper));
+        assert_eq!(explicit.score(scissors, spock), 11);
+
+        // The same game as a cycle, each move beating the two before it
+        let cyclic = Game::from_toml(
+            r#"
+            moves = [
+                { name = "rock", them = "A", me = "V", score = 1 },
+                { name = "spock", them = "E", me = "Z", score = 5 },
+                { name = "paper", them = "B", me = "W", score = 2 },
+                { name = "lizard", them = "D", me = "Y", score = 4 },
+                { name = "scissors", them = "C", me = "X", score = 3 },
+            ]
+            outcomes = { lose = { code = "X", score = 0 }, draw = { code = "Y", score = 3 }, win = { code = "Z", score = 6 } }
+            "#,
+        )?;
+        for a in explicit.moves() {
+            for b in explicit.moves() {
+                let (ca, cb) = (
+                    by_name(&cyclic, explicit.name(a)),
+                    by_name(&cyclic, explicit.name(b)),
+                );
+                assert_eq!(explicit.beats(a, b), cyclic.beats(ca, cb));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid() {
+        let game = |moves: &str, beats: &str| {
+            Game::from_toml(&format!(
+                "moves = [{}]\n{}\n[outcomes]\nlose = {{ code = \"X\", score = 0 }}\n\
+                 draw = {{ code = \"Y\", score = 3 }}\nwin = {{ code = \"Z\", score = 6 }}",
+                moves, beats
+            ))
+        };
+        let one = |name: &str, code: &str| {
+            format!(
+                "{{ name = \"{}\", them = \"{}\", me = \"{}\", score = 1 }},",
+                name, code, code
+            )
+        };
+        let two = one("a", "A") + &one("b", "B");
+        assert!(game(&one("a", "A"), "").is_ok());
+        assert!(game("", "").is_err());
+        assert!(game(&(one("a", "A") + &one("a", "B")), "").is_err());
+        // Two moves can't form a fair cycle, but can say who wins
+        assert!(game(&two, "").is_err());
+        assert!(game(&two, "[beats]\na = [\"b\"]").is_ok());
+        assert!(game(&two, "[beats]\na = [\"c\"]").is_err());
+        assert!(game(&two, "[beats]\na = [\"a\"]").is_err());
+        assert!(game(&two, "[beats]\na = [\"b\"]\nb = [\"a\"]").is_err());
+        assert!(game(&two, "[rules]").is_err());
+    }
+}
@@ -0,0 +1,81 @@
+//! Random rucksacks, and a slow but obviously correct solver to check them against.
+
+use common::Answers;
+use rand::{
+    seq::{IndexedRandom, SliceRandom},
+    Rng,
+};
+
+/// One rucksack packed from `pool`, with `shared` in both compartments and `badge` somewhere.
+fn rucksack<R: Rng + ?Sized>(rng: &mut R, pool: &[char], shared: char, badge: char) -> String {
+    let (left_pool, right_pool) = pool.split_at(pool.len() / 2);
+    let n = rng.random_range(2..=12);
+    let mut left = vec![shared];
+    let mut right = vec![shared];
+    if badge != shared {
+        left.push(badge);
+    }
+    while left.len() < n {
+        left.push(*left_pool.choose(rng).unwrap());
+    }
+    while right.len() < n {
+        right.push(*right_pool.choose(rng).unwrap());
+    }
+    left.shuffle(rng);
+    right.shuffle(rng);
+    left.into_iter().chain(right).collect()
+}
+
+/// `size` groups of three elves. Each group only has its badge in common, and each rucksack
+/// only has one item in both compartments.
+pub fn input<R: Rng + ?Sized>(rng: &mut R, size: usize) -> String {
+    let mut items = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+    let mut lines = Vec::new();
+    for _ in 0..size {
+        items.shuffle(rng);
+        let badge = items[0];
+        for pool in items[1..].chunks(17) {
+            let (shared, pool) = if rng.random_bool(0.2) {
+                (badge, &pool[1..])
+            } else {
+                (pool[0], &pool[1..])
+            };
+            lines.push(rucksack(rng, pool, shared, badge));
+        }
+    }
+    lines.join("\n")
+}
+
+fn priority(c: char) -> u32 {
+    ('a'..='z').chain('A'..='Z').position(|p| p == c).unwrap() as u32 + 1
+}
+
+/// Checks every item against every compartment.
+pub fn reference(input: &str) -> Answers {
+    let lines = input.lines().collect::<Vec<_>>();
+    let part1 = lines
+        .iter()
+        .map(|l| {
+            let (left, right) = l.split_at(l.len() / 2);
+            priority(left.chars().find(|c| right.contains(*c)).unwrap())
+        })
+        .sum::<u32>();
+    let part2 = lines
+        .chunks(3)
+        .map(|group| {
+            let badge = ('a'..='z')
+                .chain('A'..='Z')
+                .find(|c| group.iter().all(|l| l.contains(*c)))
+                .unwrap();
+            priority(badge)
+        })
+        .sum::<u32>();
+    Answers::new(part1, part2)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    common::test_against_reference!(crate::Day3, 0..30usize);
+}
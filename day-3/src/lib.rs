@@ -0,0 +1,224 @@
+pub mod generate;
+
+use common::{parse::parse_lines, Example, NoParams, ParseError, ParseErrorKind, Solution};
+use std::collections::HashSet;
+use tracing::{debug, trace};
+
+/// Splits a rucksack into its two compartments.
+pub fn split_rucksack(rucksack: &str) -> Result<(&str, &str), ParseError> {
+    let capacity = rucksack.len();
+    if !capacity.is_multiple_of(2) {
+        return Err(ParseError::in_line(
+            ParseErrorKind::Invalid("compartments are not evenly packed".to_string()),
+            rucksack,
+            rucksack,
+        ));
+    }
+
+    Ok((
+        &rucksack[0..capacity / 2],
+        &rucksack[capacity / 2..capacity],
+    ))
+}
+
+/// Checks a rucksack holds only scoreable items and splits evenly.
+pub fn parse_rucksack(line: &str) -> Result<String, ParseError> {
+    if let Some((i, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+        return Err(ParseError::in_line(
+            ParseErrorKind::Unexpected,
+            line,
+            &line[i..i + c.len_utf8()],
+        ));
+    }
+    split_rucksack(line)?;
+    Ok(line.to_string())
+}
+
+fn set_from_string(s: &str) -> HashSet<char> {
+    let mut h = HashSet::new();
+    for c in s.chars() {
+        h.insert(c);
+    }
+    h
+}
+
+/// The item found in both compartments, if there is one.
+pub fn check_rucksack(rucksack: &str) -> Result<Option<char>, ParseError> {
+    let (compartment_1, compartment_2) = split_rucksack(rucksack)?;
+    let (unique_items_1, unique_items_2) = (
+        set_from_string(compartment_1),
+        set_from_string(compartment_2),
+    );
+    let common: Vec<&char> = unique_items_1.intersection(&unique_items_2).collect();
+    Ok(match common.len() {
+        0 => None,
+        1 => Some(*common[0]),
+        _ => panic!("More than one common item found"),
+    })
+}
+
+/// The one item carried by every rucksack in a group.
+pub fn find_common_in_group(rucksacks: &[String]) -> char {
+    let sets: Vec<HashSet<char>> = rucksacks.iter().map(|r| set_from_string(r)).collect();
+
+    let mut s = sets[0].clone();
+    for item in sets.iter().skip(1) {
+        let i = s.intersection(item).cloned();
+        s = i.collect::<HashSet<char>>();
+    }
+
+    if s.len() != 1 {
+        panic!("Expected only one mutual intersection, found {}", s.len())
+    } else {
+        let v = s.iter().collect::<Vec<_>>();
+        *v[0]
+    }
+}
+
+/// The priority of an item: a-z are 1-26 and A-Z are 27-52.
+pub fn score(c: &char) -> u32 {
+    if !c.is_ascii() {
+        panic!("Cannot score non-ASCII characters")
+    }
+    if !c.is_ascii_alphabetic() {
+        panic!("Cannot score non-Alphabetic characters")
+    }
+    let n = *c as u8;
+    if n.is_ascii_lowercase() {
+        1 + (n - b'a') as u32
+    } else {
+        27 + (n - b'A') as u32
+    }
+}
+
+const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Model = Vec<String>;
+    type Params = NoParams;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "157", "70")];
+
+    fn parse_lines<I>(lines: I) -> anyhow::Result<Self::Model>
+    where
+        I: Iterator<Item = String>,
+    {
+        let rucksacks = parse_lines(lines, parse_rucksack)?;
+        debug!(rucksacks = rucksacks.len(), "parsed");
+        Ok(rucksacks)
+    }
+
+    fn part1(rucksacks: &Self::Model, _: &NoParams) -> anyhow::Result<u32> {
+        let mut total = 0;
+        for r in rucksacks {
+            if let Some(duplicate) = check_rucksack(r)? {
+                trace!(rucksack = %r, %duplicate, "packed in both compartments");
+                total += score(&duplicate);
+            }
+        }
+        Ok(total)
+    }
+
+    fn part2(rucksacks: &Self::Model, _: &NoParams) -> anyhow::Result<u32> {
+        Ok(rucksacks
+            .chunks_exact(3)
+            .map(|group| {
+                let badge = find_common_in_group(group);
+                trace!(%badge, "carried by the whole group");
+                score(&badge)
+            })
+            .sum())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn check_line() {
+        assert_eq!(check_rucksack("vJrwpWtwJgWrhcsFMMfFFhFp"), Ok(Some('p')));
+    }
+
+    #[test]
+    fn check_split_rucksack() {
+        assert_eq!(split_rucksack("rucksack"), Ok(("ruck", "sack")));
+    }
+
+    #[test]
+    fn check_split_uneven_rucksack_fails() {
+        assert!(split_rucksack("rucksacks").is_err());
+    }
+
+    #[test]
+    fn check_parse_rucksack() {
+        assert_eq!(parse_rucksack("ruck"), Ok("ruck".to_string()));
+        let e = parse_rucksack("ru3k").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (3, "3"));
+
+        let e = Day3::parse("ruck\nrucks").err().unwrap();
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 1: compartments are not evenly packed \"rucks\""
+        );
+    }
+
+    #[test]
+    fn check_score() {
+        let test_cases = [('a', 1), ('z', 26), ('A', 27), ('Z', 52)];
+        for (c, s) in test_cases {
+            assert_eq!(score(&c), s, "{} => {}", c, s);
+        }
+    }
+
+    #[test]
+    fn check_test_data() {
+        let test_data = vec![
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "PmmdzqPrVvPwwTWBwg",
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+            "ttgJtRGJQctTZtZT",
+            "CrZsJsPPZsGzwwsLwLmpwMDw",
+        ];
+
+        let mut total_score = 0;
+        for line in test_data {
+            if let Some(duplicate) = check_rucksack(line).unwrap() {
+                total_score += score(&duplicate);
+            }
+        }
+
+        assert_eq!(total_score, 157);
+    }
+
+    #[test]
+    fn check_find_common_in_group() {
+        let group: Vec<String> = vec![
+            "vJrwpWtwJgWrhcsFMMfFFhFp".to_string(),
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL".to_string(),
+            "PmmdzqPrVvPwwTWBwg".to_string(),
+        ];
+
+        let common = find_common_in_group(&group);
+
+        assert_eq!(common, 'r');
+    }
+
+    #[test]
+    fn test_solve() -> anyhow::Result<()> {
+        let answers = Day3::solve(EXAMPLE)?;
+        assert_eq!(answers.part1, "157");
+        assert_eq!(answers.part2, "70");
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+//! Random section assignments, and a slow but obviously correct solver to check them against.
+
+use common::Answers;
+use rand::Rng;
+use std::collections::HashSet;
+
+fn range<R: Rng + ?Sized>(rng: &mut R) -> (u32, u32) {
+    let lower = rng.random_range(1..=20);
+    (lower, rng.random_range(lower..=20))
+}
+
+/// `size` pairs of elves, each assigned a range of sections.
+pub fn input<R: Rng + ?Sized>(rng: &mut R, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let ((a, b), (c, d)) = (range(rng), range(rng));
+            format!("{}-{},{}-{}", a, b, c, d)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Compares each pair's sections as sets.
+pub fn reference(input: &str) -> Answers {
+    let (mut part1, mut part2) = (0, 0);
+    for line in input.lines() {
+        let sections = line
+            .split(',')
+            .map(|r| {
+                let (lower, upper) = r.split_once('-').unwrap();
+                (lower.parse::<u32>().unwrap()..=upper.parse().unwrap()).collect::<HashSet<_>>()
+            })
+            .collect::<Vec<_>>();
+        let (first, second) = (&sections[0], &sections[1]);
+        if first.is_subset(second) || second.is_subset(first) {
+            part1 += 1;
+        }
+        if !first.is_disjoint(second) {
+            part2 += 1;
+        }
+    }
+    Answers::new(part1, part2)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    common::test_against_reference!(crate::Day4, 0..100usize);
+}
@@ -0,0 +1,188 @@
+pub mod generate;
+
+use common::{
+    parse::{parse_lines, parse_number},
+    Example, Interval, NoParams, ParseError, ParseErrorKind, Solution,
+};
+use tracing::{debug, trace};
+
+/// The sections an elf is assigned.
+pub type Elf = Interval<u32>;
+
+/// Whether one range fully contains the other.
+pub fn either_contains(range1: Elf, range2: Elf) -> bool {
+    range1.contains_interval(&range2) || range2.contains_interval(&range1)
+}
+
+/// Parses one "lower-upper" range from `line`.
+pub fn parse_range(line: &str, range: &str) -> Result<Elf, ParseError> {
+    let split: Vec<&str> = range.split('-').collect();
+    if split.len() != 2 {
+        Err(ParseError::in_line(
+            ParseErrorKind::Expected("two values separated by '-'"),
+            line,
+            range,
+        ))
+    } else {
+        Ok(Interval::new(
+            parse_number(line, split[0])?,
+            parse_number(line, split[1])?,
+        ))
+    }
+}
+
+/// Parses a line of the form "a-b,c-d" into a pair of ranges.
+pub fn parse_line(line: &str) -> Result<(Elf, Elf), ParseError> {
+    let split: Vec<&str> = line.split(',').collect();
+    if split.len() != 2 {
+        Err(ParseError::in_line(
+            ParseErrorKind::Expected("two ranges separated by ','"),
+            line,
+            line,
+        ))
+    } else {
+        Ok((parse_range(line, split[0])?, parse_range(line, split[1])?))
+    }
+}
+
+const EXAMPLE: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Model = Vec<(Elf, Elf)>;
+    type Params = NoParams;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "2", "4")];
+
+    fn parse_lines<I>(lines: I) -> anyhow::Result<Self::Model>
+    where
+        I: Iterator<Item = String>,
+    {
+        let pairs = parse_lines(lines, parse_line)?;
+        debug!(pairs = pairs.len(), "parsed");
+        Ok(pairs)
+    }
+
+    fn part1(pairs: &Self::Model, _: &NoParams) -> anyhow::Result<usize> {
+        Ok(pairs
+            .iter()
+            .filter(|(elf1, elf2)| either_contains(*elf1, *elf2))
+            .inspect(|(elf1, elf2)| trace!(?elf1, ?elf2, "one contains the other"))
+            .count())
+    }
+
+    fn part2(pairs: &Self::Model, _: &NoParams) -> anyhow::Result<usize> {
+        Ok(pairs
+            .iter()
+            .filter(|(elf1, elf2)| elf1.overlaps(elf2))
+            .inspect(|(elf1, elf2)| trace!(?elf1, ?elf2, "overlap"))
+            .count())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn elf((lower, upper): (u32, u32)) -> Elf {
+        Interval::new(lower, upper)
+    }
+
+    #[test]
+    fn check_outer_containers_inner() {
+        let test_data = vec![
+            ((2, 4), (6, 8), false),
+            ((2, 3), (4, 5), false),
+            ((5, 7), (7, 9), false),
+            ((2, 8), (3, 7), true),
+            ((6, 6), (4, 6), false),
+            ((2, 6), (4, 8), false),
+        ];
+
+        for (elf1, elf2, contained) in test_data {
+            assert_eq!(
+                elf(elf1).contains_interval(&elf(elf2)),
+                contained,
+                "{:?} {:?} {}",
+                elf1,
+                elf2,
+                contained
+            );
+        }
+    }
+
+    #[test]
+    fn check_is_contained() {
+        let test_data = vec![
+            ((2, 4), (6, 8), false),
+            ((2, 3), (4, 5), false),
+            ((5, 7), (7, 9), false),
+            ((2, 8), (3, 7), true),
+            ((6, 6), (4, 6), true),
+            ((2, 6), (4, 8), false),
+        ];
+
+        for (elf1, elf2, contained) in test_data {
+            assert_eq!(either_contains(elf(elf1), elf(elf2)), contained);
+        }
+    }
+
+    #[test]
+    fn check_overlap_at_all() {
+        let test_data = vec![
+            ((2, 4), (6, 8), false),
+            ((2, 3), (4, 5), false),
+            ((5, 7), (7, 9), true),
+            ((2, 8), (3, 7), true),
+            ((6, 6), (4, 6), true),
+            ((2, 6), (4, 8), true),
+        ];
+
+        for (elf1, elf2, overlaps) in test_data {
+            assert_eq!(
+                elf(elf1).overlaps(&elf(elf2)),
+                overlaps,
+                "{:?} {:?}",
+                elf1,
+                elf2
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse() -> Result<(), ParseError> {
+        let test_data = vec![("2-4,6-8", ((2, 4), (6, 8)))];
+
+        for (line, ranges) in test_data {
+            assert_eq!(parse_line(line)?, (elf(ranges.0), elf(ranges.1)));
+        }
+
+        let e = parse_line("2-4,6-x").unwrap_err();
+        assert_eq!((e.kind, e.column), (ParseErrorKind::InvalidNumber, 7));
+        let e = parse_line("2-4,6").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (5, "6"));
+        let e = Day4::parse("2-4,6-8\n2-4").err().unwrap();
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 1: expected two ranges separated by ',', found \"2-4\""
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve() -> anyhow::Result<()> {
+        let answers = Day4::solve(EXAMPLE)?;
+        assert_eq!(answers.part1, "2");
+        assert_eq!(answers.part2, "4");
+        Ok(())
+    }
+}
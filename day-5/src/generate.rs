@@ -0,0 +1,103 @@
+//! Random crate stacks and rearrangements, and a slow but obviously correct solver to check them
+//! against.
+
+use common::Answers;
+use rand::Rng;
+
+fn draw(stacks: &[Vec<char>]) -> Vec<String> {
+    let height = stacks.iter().map(|s| s.len()).max().unwrap_or(0);
+    let mut lines = (0..height)
+        .rev()
+        .map(|row| {
+            stacks
+                .iter()
+                .map(|s| match s.get(row) {
+                    Some(c) => format!("[{}]", c),
+                    None => "   ".to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>();
+    lines.push(
+        (1..=stacks.len())
+            .map(|i| format!(" {} ", i))
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+    lines
+}
+
+/// Between two and nine stacks of up to `size` crates, and `size` moves that can all be made.
+/// Stacks may start, or end up, empty.
+pub fn input<R: Rng + ?Sized>(rng: &mut R, size: usize) -> String {
+    let mut stacks = (0..rng.random_range(2..=9))
+        .map(|_| {
+            (0..rng.random_range(0..=size))
+                .map(|_| rng.random_range('A'..='Z'))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let mut lines = draw(&stacks);
+    lines.push(String::new());
+
+    for _ in 0..size {
+        let from = rng.random_range(0..stacks.len());
+        if stacks[from].is_empty() {
+            continue;
+        }
+        let to = (from + rng.random_range(1..stacks.len())) % stacks.len();
+        let how_many = rng.random_range(1..=stacks[from].len());
+        // Keep the stacks valid for the next move; moving one at a time or all at once doesn't
+        // change how many end up where
+        let at = stacks[from].len() - how_many;
+        let moved = stacks[from].split_off(at);
+        stacks[to].extend(moved);
+        lines.push(format!("move {} from {} to {}", how_many, from + 1, to + 1));
+    }
+    lines.join("\n")
+}
+
+/// Reads the stacks from their drawing and replays the moves both ways.
+pub fn reference(input: &str) -> Answers {
+    let (drawing, moves) = input.split_once("\n\n").unwrap_or((input, ""));
+    let drawing = drawing.lines().collect::<Vec<_>>();
+    let count = drawing.last().unwrap().split_whitespace().count();
+    let mut stacks = vec![Vec::new(); count];
+    for line in drawing[..drawing.len() - 1].iter().rev() {
+        for (i, stack) in stacks.iter_mut().enumerate() {
+            if let Some(c) = line
+                .chars()
+                .nth(1 + 4 * i)
+                .filter(|c| c.is_ascii_uppercase())
+            {
+                stack.push(c);
+            }
+        }
+    }
+
+    let (mut one_at_a_time, mut all_at_once) = (stacks.clone(), stacks);
+    for line in moves.lines() {
+        let words = line.split(' ').collect::<Vec<_>>();
+        let how_many = words[1].parse::<usize>().unwrap();
+        let from = words[3].parse::<usize>().unwrap() - 1;
+        let to = words[5].parse::<usize>().unwrap() - 1;
+        for _ in 0..how_many {
+            let c = one_at_a_time[from].pop().unwrap();
+            one_at_a_time[to].push(c);
+        }
+        let at = all_at_once[from].len() - how_many;
+        let moved = all_at_once[from].split_off(at);
+        all_at_once[to].extend(moved);
+    }
+
+    let tops = |stacks: Vec<Vec<char>>| stacks.iter().filter_map(|s| s.last()).collect::<String>();
+    Answers::new(tops(one_at_a_time), tops(all_at_once))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    common::test_against_reference!(crate::Day5, 0..30usize);
+}
@@ -0,0 +1,295 @@
+pub mod generate;
+pub mod visualise;
+
+use anyhow::anyhow;
+use common::{parse::parse_number, Example, NoParams, ParseError, ParseErrorKind, Solution};
+use core::fmt;
+use std::{collections::BTreeMap, fmt::Write};
+use tracing::{debug, trace};
+
+/// A stack of crates, bottom first.
+pub type Column = Vec<char>;
+
+/// The stacks of crates, keyed by their label.
+#[derive(Debug, Clone, Default)]
+pub struct Board {
+    pub columns: BTreeMap<usize, Column>,
+}
+
+impl Board {
+    pub fn new() -> Self {
+        Board {
+            columns: BTreeMap::new(),
+        }
+    }
+
+    /// The crate on top of each stack, in label order.
+    pub fn tops(&self) -> String {
+        self.columns.values().filter_map(|c| c.last()).collect()
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut keys = self.columns.keys().collect::<Vec<_>>();
+        keys.sort();
+        for k in keys {
+            f.write_fmt(format_args!("{} : {:?}", k, self.columns[k]))?;
+            f.write_char('\n')?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Moves `how_many` crates from stack `from` to stack `to`.
+#[derive(Debug)]
+pub struct Move {
+    pub how_many: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+impl Move {
+    pub fn new(how_many: usize, from: usize, to: usize) -> Self {
+        Move { how_many, from, to }
+    }
+}
+
+pub fn parse_move(line: &str) -> Result<Move, ParseError> {
+    let words = line.split_whitespace().collect::<Vec<_>>();
+    match words.as_slice() {
+        ["move", how_many, "from", from, "to", to] => Ok(Move::new(
+            parse_number(line, how_many)?,
+            parse_number(line, from)?,
+            parse_number(line, to)?,
+        )),
+        _ => Err(ParseError::in_line(
+            ParseErrorKind::Expected("\"move N from A to B\""),
+            line,
+            line,
+        )),
+    }
+}
+
+/// Parses the drawing of the stacks, ending with the line of labels.
+pub fn parse_board(lines: Vec<&str>) -> Result<Board, ParseError> {
+    let mut board_lines = lines.iter().rev();
+
+    // Last line should be the column labels, so get the number of columns from that
+    let column_labels_line = match board_lines.next() {
+        Some(s) => s,
+        None => {
+            return Err(ParseError::new(
+                ParseErrorKind::Expected("column labels"),
+                "",
+            ))
+        }
+    };
+
+    let column_labels = column_labels_line
+        .chars()
+        .enumerate()
+        .filter(|(_, c)| c.is_numeric())
+        .map(|(e, c)| (e, (c as u8 - b'0') as usize))
+        .collect::<Vec<_>>();
+
+    let mut board = Board::new();
+    // Every labelled stack exists, even if it starts empty
+    for (_, name) in &column_labels {
+        board.columns.insert(*name, Column::new());
+    }
+
+    for line in board_lines {
+        let chars = line.chars().collect::<Vec<char>>();
+        for (idx, name) in &column_labels {
+            // Trailing spaces may have been trimmed from short lines
+            match chars.get(*idx) {
+                Some(c) if !c.is_whitespace() => board.columns.get_mut(name).unwrap().push(*c),
+                _ => (),
+            }
+        }
+    }
+    Ok(board)
+}
+
+/// Moves the crates one at a time, as the CrateMover 9000 does.
+pub fn play_move(board: &mut Board, played: &Move) -> anyhow::Result<()> {
+    let columns = &mut board.columns;
+
+    if let Some(from_column) = columns.get(&played.from) {
+        if from_column.len() < played.how_many {
+            return Err(anyhow!("Invalid move"));
+        }
+    } else {
+        return Err(anyhow!("Invalid column"));
+    }
+    if !columns.contains_key(&played.to) {
+        return Err(anyhow!("Invalid column"));
+    }
+
+    for _ in 0..played.how_many {
+        let popped = columns.get_mut(&played.from).unwrap().pop().unwrap();
+        columns.get_mut(&played.to).unwrap().push(popped);
+    }
+
+    Ok(())
+}
+
+/// Moves the crates all at once, keeping their order, as the CrateMover 9001 does.
+pub fn play_move_2(board: &mut Board, played: &Move) -> anyhow::Result<()> {
+    let columns = &mut board.columns;
+
+    if let Some(from_column) = columns.get(&played.from) {
+        if from_column.len() < played.how_many {
+            return Err(anyhow!("Invalid move"));
+        }
+    } else {
+        return Err(anyhow!("Invalid column"));
+    }
+    if !columns.contains_key(&played.to) {
+        return Err(anyhow!("Invalid column"));
+    }
+
+    let mut temp = vec![];
+    for _ in 0..played.how_many {
+        let popped = columns.get_mut(&played.from).unwrap().pop().unwrap();
+        temp.push(popped);
+    }
+
+    for _ in 0..played.how_many {
+        let popped = temp.pop().unwrap();
+        columns.get_mut(&played.to).unwrap().push(popped)
+    }
+
+    Ok(())
+}
+
+const EXAMPLE: &str = "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2";
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    /// The starting stacks, and the moves to make
+    type Model = (Board, Vec<Move>);
+    type Params = NoParams;
+    type Part1 = String;
+    type Part2 = String;
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "CMZ", "MCD")];
+
+    fn parse_lines<I>(mut lines: I) -> anyhow::Result<Self::Model>
+    where
+        I: Iterator<Item = String>,
+    {
+        let board_lines = lines
+            .by_ref()
+            .take_while(|l| !l.is_empty())
+            .collect::<Vec<_>>();
+        let first_move = board_lines.len() + 2;
+        let board = parse_board(board_lines.iter().map(|l| l.as_str()).collect())?;
+        let moves = lines
+            .enumerate()
+            .map(|(i, l)| parse_move(&l).map_err(|e| e.at_line(first_move + i)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        debug!(stacks = board.columns.len(), moves = moves.len(), "parsed");
+        Ok((board, moves))
+    }
+
+    fn part1((board, moves): &Self::Model, _: &NoParams) -> anyhow::Result<String> {
+        let mut board = board.clone();
+        for m in moves {
+            play_move(&mut board, m)?;
+            trace!(?m, tops = %board.tops());
+        }
+        Ok(board.tops())
+    }
+
+    fn part2((board, moves): &Self::Model, _: &NoParams) -> anyhow::Result<String> {
+        let mut board = board.clone();
+        for m in moves {
+            play_move_2(&mut board, m)?;
+            trace!(?m, tops = %board.tops());
+        }
+        Ok(board.tops())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let input = EXAMPLE;
+        let mut lines = input.lines();
+
+        let board_lines = lines
+            .by_ref()
+            .take_while(|l| !l.is_empty())
+            .collect::<Vec<_>>();
+        let mut board = parse_board(board_lines)?;
+        assert_eq!(board.columns.len(), 3);
+        assert_eq!(board.columns[&1], vec!['Z', 'N']);
+        assert_eq!(board.columns[&2], vec!['M', 'C', 'D']);
+        assert_eq!(board.columns[&3], vec!['P']);
+
+        for line in lines {
+            let m = parse_move(line)?;
+            play_move(&mut board, &m)?;
+        }
+        assert_eq!(board.columns[&1], vec!['C']);
+        assert_eq!(board.columns[&2], vec!['M']);
+        assert_eq!(board.columns[&3], vec!['P', 'D', 'N', 'Z']);
+        assert_eq!(board.tops(), "CMZ");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test2() -> anyhow::Result<()> {
+        let input = EXAMPLE;
+        let mut lines = input.lines();
+
+        let board_lines = lines
+            .by_ref()
+            .take_while(|l| !l.is_empty())
+            .collect::<Vec<_>>();
+        let mut board = parse_board(board_lines)?;
+        assert_eq!(board.columns.len(), 3);
+        assert_eq!(board.columns[&1], vec!['Z', 'N']);
+        assert_eq!(board.columns[&2], vec!['M', 'C', 'D']);
+        assert_eq!(board.columns[&3], vec!['P']);
+
+        for line in lines {
+            let m = parse_move(line)?;
+            play_move_2(&mut board, &m)?;
+        }
+        assert_eq!(board.columns[&1], vec!['M']);
+        assert_eq!(board.columns[&2], vec!['C']);
+        assert_eq!(board.columns[&3], vec!['P', 'Z', 'N', 'D']);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bad_moves() {
+        let e = parse_move("move 1 from x to 3").unwrap_err();
+        assert_eq!((e.kind, e.column), (ParseErrorKind::InvalidNumber, 13));
+        assert!(parse_move("move 1 to 3").is_err());
+
+        let e = Day5::parse("[A]\n 1 \n\nmove 1 from 1 to 1\nmove one from 1 to 1")
+            .err()
+            .unwrap();
+        assert_eq!(e.to_string(), "line 5, column 6: invalid number \"one\"");
+    }
+}
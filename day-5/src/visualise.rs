@@ -0,0 +1,173 @@
+use crate::{play_move, play_move_2, Board, Day5, Move};
+use common::{
+    render::{Colour, Frame, Glyph, Part, Player, Visualise},
+    NoParams, Vec2,
+};
+use std::io::Write;
+
+/// The stacks drawn as the puzzle draws them, with room above for every crate to end up on one
+/// stack, and the crates just moved picked out.
+pub struct BoardFrame<'a> {
+    pub board: &'a Board,
+    pub played: Option<&'a Move>,
+    pub caption: String,
+}
+
+impl BoardFrame<'_> {
+    /// The row of labels, below the tallest a stack can get.
+    fn label_row(&self) -> isize {
+        self.board.columns.values().map(|c| c.len()).sum::<usize>() as isize
+    }
+}
+
+impl Frame for BoardFrame<'_> {
+    fn bounds(&self) -> (Vec2, Vec2) {
+        let width = 4 * self.board.columns.len() as isize - 1;
+        (Vec2::origin(), Vec2::new(width - 1, self.label_row()))
+    }
+
+    fn glyph(&self, p: Vec2) -> Glyph {
+        let Some((label, column)) = usize::try_from(p.x / 4)
+            .ok()
+            .and_then(|i| self.board.columns.iter().nth(i))
+        else {
+            return Glyph::BLANK;
+        };
+        let touched = |label| {
+            self.played
+                .is_some_and(|m| m.from == label || m.to == label)
+        };
+
+        if p.y == self.label_row() {
+            return match (p.x % 4, char::from_digit(*label as u32, 10)) {
+                (1, Some(c)) if touched(*label) => Glyph::coloured(c, Colour::Cyan),
+                (1, Some(c)) => Glyph::plain(c),
+                _ => Glyph::BLANK,
+            };
+        }
+        let level = (self.label_row() - 1 - p.y) as usize;
+        let Some(c) = column.get(level) else {
+            return Glyph::BLANK;
+        };
+        let ch = match p.x % 4 {
+            0 => '[',
+            1 => *c,
+            2 => ']',
+            _ => return Glyph::BLANK,
+        };
+        match self.played {
+            Some(m) if m.to == *label && level + m.how_many >= column.len() => {
+                Glyph::coloured(ch, Colour::Yellow)
+            }
+            _ => Glyph::plain(ch),
+        }
+    }
+
+    /// The top of the stack crates were just moved onto.
+    fn focus(&self) -> Option<Vec2> {
+        let played = self.played?;
+        let (i, (_, column)) = self
+            .board
+            .columns
+            .iter()
+            .enumerate()
+            .find(|(_, (label, _))| **label == played.to)?;
+        Some(Vec2::new(
+            4 * i as isize + 1,
+            self.label_row() - column.len() as isize,
+        ))
+    }
+
+    fn caption(&self) -> String {
+        self.caption.clone()
+    }
+}
+
+impl Visualise for Day5 {
+    /// Shows the stacks after each move.
+    fn visualise<W: Write>(
+        (board, moves): &Self::Model,
+        _: &NoParams,
+        part: Part,
+        player: &mut Player<W>,
+    ) -> anyhow::Result<()> {
+        let play: fn(&mut Board, &Move) -> anyhow::Result<()> = match part {
+            Part::One => play_move,
+            Part::Two => play_move_2,
+        };
+
+        let mut board = board.clone();
+        player.show(&BoardFrame {
+            board: &board,
+            played: None,
+            caption: "Starting stacks".to_string(),
+        })?;
+        for (i, m) in moves.iter().enumerate() {
+            play(&mut board, m)?;
+            player.show(&BoardFrame {
+                board: &board,
+                played: Some(m),
+                caption: format!(
+                    "Move {}/{}: move {} from {} to {}, tops {}",
+                    i + 1,
+                    moves.len(),
+                    m.how_many,
+                    m.from,
+                    m.to,
+                    board.tops()
+                ),
+            })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::EXAMPLE;
+    use common::{render::Viewport, Solution};
+
+    #[test]
+    fn test_frame() -> anyhow::Result<()> {
+        let (mut board, moves) = Day5::parse(EXAMPLE)?;
+        for m in &moves {
+            play_move(&mut board, m)?;
+        }
+        let frame = BoardFrame {
+            board: &board,
+            played: moves.last(),
+            caption: String::new(),
+        };
+        let drawn = "
+
+        [Z]
+        [N]
+        [D]
+[C] [M] [P]
+ 1   2   3
+";
+        let rendered = Viewport::new(20, 10).render(&frame, false);
+        assert_eq!(
+            rendered.lines().map(str::trim_end).collect::<Vec<_>>(),
+            drawn.lines().collect::<Vec<_>>()
+        );
+        assert_eq!(frame.focus(), Some(Vec2::new(5, 5)));
+
+        // Too short to show every row, so the top of stack 2 stays in view
+        let cropped = Viewport::new(20, 3).render(&frame, false);
+        assert_eq!(cropped.lines().nth(1), Some("[C] [M] [P]"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_visualise() -> anyhow::Result<()> {
+        let model = Day5::parse(EXAMPLE)?;
+        let mut out = Vec::new();
+        let mut player = Player::new(&mut out, Viewport::default(), 0, true);
+        Day5::visualise(&model, &NoParams {}, Part::Two, &mut player)?;
+        assert_eq!(player.shown(), 1 + model.1.len());
+        assert!(String::from_utf8(out)?.contains("Move 4/4: move 1 from 1 to 2, tops MCD"));
+        Ok(())
+    }
+}
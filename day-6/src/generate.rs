@@ -0,0 +1,38 @@
+//! Random datastreams, and a slow but obviously correct solver to check them against.
+
+use common::Answers;
+use rand::{seq::SliceRandom, Rng};
+use std::collections::HashSet;
+
+/// About `size` characters drawn from a few letters, so markers are rare, with a run of 14
+/// distinct letters somewhere to make sure both parts find one.
+pub fn input<R: Rng + ?Sized>(rng: &mut R, size: usize) -> String {
+    let mut stream = (0..size)
+        .map(|_| rng.random_range('a'..='e'))
+        .collect::<Vec<_>>();
+    let mut marker = ('a'..='z').collect::<Vec<_>>();
+    marker.shuffle(rng);
+    let at = rng.random_range(0..=stream.len());
+    stream.splice(at..at, marker.into_iter().take(14));
+    stream.into_iter().collect()
+}
+
+/// Checks every window for duplicates.
+pub fn reference(input: &str) -> Answers {
+    let chars = input.trim().chars().collect::<Vec<_>>();
+    let marker = |length: usize| {
+        chars
+            .windows(length)
+            .position(|w| w.iter().collect::<HashSet<_>>().len() == length)
+            .unwrap()
+            + length
+    };
+    Answers::new(marker(4), marker(14))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    common::test_against_reference!(crate::Day6, 0..200usize);
+}
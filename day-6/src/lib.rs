@@ -0,0 +1,118 @@
+pub mod generate;
+
+use anyhow::anyhow;
+use common::{Example, NoParams, Solution};
+use std::collections::{HashSet, VecDeque};
+use tracing::debug;
+
+/// Watches a stream of characters for a run of `length` distinct ones.
+pub struct Detector {
+    queue: VecDeque<char>,
+    capacity: usize,
+}
+
+impl Detector {
+    pub fn new(length: usize) -> Self {
+        Detector {
+            queue: VecDeque::new(),
+            capacity: length,
+        }
+    }
+
+    pub fn push_and_check(&mut self, c: char) -> bool {
+        if self.queue.len() == self.capacity {
+            self.queue.pop_front();
+        }
+        self.queue.push_back(c);
+        let set_length = self.queue.iter().collect::<HashSet<&char>>().len();
+
+        set_length == self.capacity
+    }
+
+    /// The number of characters read when the first marker ends.
+    pub fn detect(&mut self, input: &str) -> Option<usize> {
+        if input.len() < self.capacity {
+            return None;
+        }
+        for (i, c) in input.chars().enumerate() {
+            if self.push_and_check(c) {
+                return Some(i + 1);
+            }
+        }
+        None
+    }
+}
+
+pub fn find_marker(input: &str, length: usize) -> anyhow::Result<usize> {
+    let mut detector = Detector::new(length);
+    let end = detector
+        .detect(input)
+        .ok_or_else(|| anyhow!("No marker of length {} found", length))?;
+    let marker = input.chars().skip(end - length).collect::<String>();
+    debug!(length, end, marker = &marker[..length], "found a marker");
+    Ok(end)
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Model = String;
+    type Params = NoParams;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const EXAMPLES: &'static [Example] = &[
+        Example::new("mjqjpqmgbljsphdztnvjfqwrcgsmlb", "7", "19"),
+        Example::new("bvwbjplbgvbhsrlpgdmjqwftvncz", "5", "23"),
+        Example::new("nppdvjthqldpwncqszvftbrmjlhg", "6", "23"),
+        Example::new("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", "10", "29"),
+        Example::new("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", "11", "26"),
+    ];
+
+    fn parse_lines<I>(mut lines: I) -> anyhow::Result<String>
+    where
+        I: Iterator<Item = String>,
+    {
+        // The datastream is all on the first line
+        let datastream = lines.next().unwrap_or_default().trim().to_string();
+        debug!(length = datastream.len(), "parsed");
+        Ok(datastream)
+    }
+
+    fn part1(input: &String, _: &NoParams) -> anyhow::Result<usize> {
+        find_marker(input, 4)
+    }
+
+    fn part2(input: &String, _: &NoParams) -> anyhow::Result<usize> {
+        find_marker(input, 14)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let test_data = vec![
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", Some(5)),
+            ("nppdvjthqldpwncqszvftbrmjlhg", Some(6)),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", Some(10)),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", Some(11)),
+            ("abcd", Some(4)),
+            ("abc", None),
+        ];
+        for (input, expected) in test_data {
+            let mut detector = Detector::new(4);
+            assert_eq!(detector.detect(input), expected, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_solve() -> anyhow::Result<()> {
+        let answers = Day6::solve("mjqjpqmgbljsphdztnvjfqwrcgsmlb")?;
+        assert_eq!(answers.part1, "7");
+        assert_eq!(answers.part2, "19");
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+//! Random terminal sessions, and a slow but obviously correct solver to check them against.
+
+use common::Answers;
+use rand::{seq::SliceRandom, Rng};
+use std::collections::{HashMap, HashSet};
+
+enum Entry {
+    File(String, usize),
+    Dir(String, Vec<Entry>),
+}
+
+fn name<R: Rng + ?Sized>(rng: &mut R, taken: &mut HashSet<String>) -> String {
+    loop {
+        let name = (0..rng.random_range(1..=3))
+            .map(|_| rng.random_range('a'..='e'))
+            .collect::<String>();
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+/// A directory holding about `budget` entries in all, with files of up to `max_file` bytes.
+fn tree<R: Rng + ?Sized>(rng: &mut R, budget: usize, max_file: usize) -> Vec<Entry> {
+    let mut taken = HashSet::new();
+    let mut entries = Vec::new();
+    let mut budget = budget;
+    while budget > 0 {
+        budget -= 1;
+        if rng.random_bool(0.3) {
+            let inner = rng.random_range(0..=budget);
+            budget -= inner;
+            let name = name(rng, &mut taken);
+            entries.push(Entry::Dir(name, tree(rng, inner, max_file)));
+        } else {
+            let name = name(rng, &mut taken);
+            entries.push(Entry::File(name, rng.random_range(1..=max_file)));
+        }
+    }
+    entries
+}
+
+fn transcript<R: Rng + ?Sized>(rng: &mut R, entries: &mut [Entry], lines: &mut Vec<String>) {
+    lines.push("$ ls".to_string());
+    entries.shuffle(rng);
+    for entry in entries.iter() {
+        lines.push(match entry {
+            Entry::File(name, size) => format!("{} {}", size, name),
+            Entry::Dir(name, _) => format!("dir {}", name),
+        });
+    }
+    entries.shuffle(rng);
+    for entry in entries.iter_mut() {
+        if let Entry::Dir(name, inner) = entry {
+            lines.push(format!("$ cd {}", name));
+            transcript(rng, inner, lines);
+            lines.push("$ cd ..".to_string());
+        }
+    }
+}
+
+/// A session that lists every directory once, in a tree of about `size` files and directories
+/// that fits on the 70000000 byte disk.
+pub fn input<R: Rng + ?Sized>(rng: &mut R, size: usize) -> String {
+    let mut root = tree(rng, size, 69_000_000 / size.max(1));
+    let mut lines = vec!["$ cd /".to_string()];
+    transcript(rng, &mut root, &mut lines);
+    lines.join("\n")
+}
+
+/// Lists every file with its full path, then adds up the ones under each directory.
+pub fn reference(input: &str) -> Answers {
+    let mut path = Vec::new();
+    let mut dirs = HashSet::new();
+    let mut files = HashMap::new();
+    for line in input.lines() {
+        let words = line.split(' ').collect::<Vec<_>>();
+        match words.as_slice() {
+            ["$", "cd", ".."] => {
+                path.pop();
+            }
+            ["$", "cd", dir] => {
+                path.push(dir.to_string());
+                dirs.insert(path.clone());
+            }
+            ["$", "ls"] | ["dir", _] => (),
+            [size, name] => {
+                let mut file = path.clone();
+                file.push(name.to_string());
+                files.insert(file, size.parse::<usize>().unwrap());
+            }
+            _ => panic!("Unexpected line {}", line),
+        }
+    }
+
+    let sizes = dirs
+        .iter()
+        .map(|dir| {
+            files
+                .iter()
+                .filter(|(file, _)| file.starts_with(dir))
+                .map(|(_, size)| size)
+                .sum::<usize>()
+        })
+        .collect::<Vec<_>>();
+    let used = files.values().sum::<usize>();
+    let needed = 30000000 - (70000000 - used).min(30000000);
+    Answers::new(
+        sizes.iter().filter(|s| **s <= 100000).sum::<usize>(),
+        sizes.iter().filter(|s| **s >= needed).min().unwrap(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    common::test_against_reference!(crate::Day7, 0..60usize);
+}
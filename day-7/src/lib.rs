@@ -0,0 +1,253 @@
+pub mod generate;
+
+use anyhow::anyhow;
+use common::{parse::parse_number, Details, Example, ParseError, ParseErrorKind, Solution};
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::{debug, trace};
+
+/// One line of terminal output.
+#[derive(PartialEq, Debug)]
+pub enum ParsedLine {
+    Cd(String),
+    Ls,
+    File(usize),
+    Dir(String),
+}
+
+pub fn parse_line(line: &str) -> Result<ParsedLine, ParseError> {
+    let split = line.split_whitespace().collect::<Vec<_>>();
+
+    match split.as_slice() {
+        ["dir", name] => Ok(ParsedLine::Dir(name.to_string())),
+        ["$", "ls"] => Ok(ParsedLine::Ls),
+        ["$", "cd", dir] => Ok(ParsedLine::Cd(dir.to_string())),
+        ["$", command, ..] => Err(ParseError::in_line(
+            ParseErrorKind::Unexpected,
+            line,
+            command,
+        )),
+        [digits, _name] => Ok(ParsedLine::File(parse_number(line, digits)?)),
+        _ => Err(ParseError::in_line(
+            ParseErrorKind::Expected("a command, directory or file"),
+            line,
+            line,
+        )),
+    }
+}
+
+/// Replays a terminal session, returning the total size of every directory, keyed by its path.
+pub fn walk_dirs<I, S>(lines: I) -> Result<HashMap<Vec<String>, usize>, ParseError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut dir_sizes: HashMap<Vec<String>, usize> = HashMap::new();
+    let mut current_dir = Vec::<String>::new();
+    let mut dir_stack = Vec::<Vec<String>>::new();
+    for (i, line) in lines.into_iter().enumerate() {
+        let line = line.as_ref();
+        let pl = parse_line(line).map_err(|e| e.at_line(i + 1))?;
+        match pl {
+            ParsedLine::Cd(dir) => {
+                trace!(?current_dir, %dir, "cd");
+                if dir == *".." {
+                    if !current_dir.is_empty() {
+                        current_dir.pop();
+                        dir_stack.pop();
+                    } else {
+                        return Err(ParseError::in_line(
+                            ParseErrorKind::Invalid("cannot cd any farther".to_string()),
+                            line,
+                            line,
+                        )
+                        .at_line(i + 1));
+                    }
+                } else {
+                    current_dir.push(dir);
+                    // Record it now, so directories without any files still count
+                    dir_sizes.entry(current_dir.clone()).or_default();
+                    dir_stack.push(current_dir.clone());
+                }
+            }
+            ParsedLine::Ls => {}
+            ParsedLine::File(size) => {
+                for entry in dir_stack.clone() {
+                    *dir_sizes.entry(entry).or_default() += size;
+                }
+            }
+            ParsedLine::Dir(_) => {}
+        }
+    }
+    Ok(dir_sizes)
+}
+
+/// The size of the root, which contains everything else.
+pub fn used_space(dir_sizes: &HashMap<Vec<String>, usize>) -> usize {
+    dir_sizes.values().max().copied().unwrap_or_default()
+}
+
+const EXAMPLE: &str = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k";
+
+/// The puzzle's settings.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    /// The largest directory part 1 counts
+    pub small_dir: usize,
+    /// The size of the whole disk
+    pub disk_size: usize,
+    /// How much free space the update needs
+    pub update_size: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            small_dir: 100000,
+            disk_size: 70000000,
+            update_size: 30000000,
+        }
+    }
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    /// Total size of each directory, keyed by its path
+    type Model = HashMap<Vec<String>, usize>;
+    type Params = Params;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "95437", "24933642")];
+
+    fn parse_lines<I>(lines: I) -> anyhow::Result<Self::Model>
+    where
+        I: Iterator<Item = String>,
+    {
+        let dir_sizes = walk_dirs(lines)?;
+        debug!(directories = dir_sizes.len(), "parsed");
+        Ok(dir_sizes)
+    }
+
+    fn part1(dir_sizes: &Self::Model, params: &Params) -> anyhow::Result<usize> {
+        Ok(dir_sizes
+            .values()
+            .filter(|size| **size <= params.small_dir)
+            .sum())
+    }
+
+    fn part2(dir_sizes: &Self::Model, params: &Params) -> anyhow::Result<usize> {
+        let unused = params
+            .disk_size
+            .checked_sub(used_space(dir_sizes))
+            .ok_or_else(|| anyhow!("The files don't fit on the disk"))?;
+        debug!(unused, needed = params.update_size, "free space");
+
+        let sufficient = dir_sizes
+            .values()
+            .filter(|s| unused + *s >= params.update_size);
+        sufficient
+            .min()
+            .copied()
+            .ok_or_else(|| anyhow!("No directory frees enough space"))
+    }
+
+    fn details(dir_sizes: &Self::Model, _: &Params) -> anyhow::Result<Details> {
+        let mut details = Details::new();
+        details.insert("directories".to_string(), dir_sizes.len().into());
+        details.insert("used_space".to_string(), used_space(dir_sizes).into());
+        Ok(details)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> anyhow::Result<()> {
+        let mut it = EXAMPLE.lines();
+        let mut pl = parse_line(it.next().unwrap())?;
+        assert_eq!(pl, ParsedLine::Cd("/".to_string()));
+        pl = parse_line(it.next().unwrap())?;
+        assert_eq!(pl, ParsedLine::Ls);
+        pl = parse_line(it.next().unwrap())?;
+        assert_eq!(pl, ParsedLine::Dir("a".to_string()));
+        pl = parse_line(it.next().unwrap())?;
+        assert_eq!(pl, ParsedLine::File(14848514));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bad_lines() {
+        let e = parse_line("$ rm -rf /").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (3, "rm"));
+        let e = parse_line("12k b.txt").unwrap_err();
+        assert_eq!(e.kind, ParseErrorKind::InvalidNumber);
+        assert!(parse_line("$").is_err());
+
+        let e = walk_dirs("$ cd /\n$ cd ..\n$ cd ..".lines()).unwrap_err();
+        assert_eq!(e.line, 3);
+    }
+
+    #[test]
+    fn test_sum() -> anyhow::Result<()> {
+        let it = EXAMPLE.lines();
+        let dir_sizes = walk_dirs(it)?;
+        let mut total_size = 0;
+        for (_dir, size) in dir_sizes {
+            if size <= 100000 {
+                total_size += size;
+            }
+        }
+        assert_eq!(total_size, 95437);
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve() -> anyhow::Result<()> {
+        let answers = Day7::solve(EXAMPLE)?;
+        assert_eq!(answers.part1, "95437");
+        assert_eq!(answers.part2, "24933642");
+
+        let smaller_update = Params {
+            update_size: 21700000,
+            ..Params::default()
+        };
+        let answers = Day7::solve_with(EXAMPLE, &smaller_update)?;
+        assert_eq!(answers.part2, "94853");
+
+        let tiny_disk = Params {
+            disk_size: 1000,
+            ..Params::default()
+        };
+        assert!(Day7::solve_with(EXAMPLE, &tiny_disk).is_err());
+        Ok(())
+    }
+}
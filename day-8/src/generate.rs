@@ -0,0 +1,63 @@
+//! Random groves, and a slow but obviously correct solver to check them against.
+
+use common::Answers;
+use rand::Rng;
+
+/// A grove of random heights, at most `size` trees wide and high, and not necessarily square.
+pub fn input<R: Rng + ?Sized>(rng: &mut R, size: usize) -> String {
+    let width = rng.random_range(1..=size.max(1));
+    let height = rng.random_range(1..=size.max(1));
+    (0..height)
+        .map(|_| {
+            (0..width)
+                .map(|_| char::from_digit(rng.random_range(0..10), 10).unwrap())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Walks out from every tree in each direction, one step at a time.
+pub fn reference(input: &str) -> Answers {
+    let rows = input
+        .lines()
+        .map(|l| l.bytes().map(|b| b - b'0').collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let (height, width) = (rows.len() as i32, rows[0].len() as i32);
+    let at = |x: i32, y: i32| rows[y as usize][x as usize];
+
+    let mut visible = 0;
+    let mut best = 0;
+    for y in 0..height {
+        for x in 0..width {
+            let mut seen = false;
+            let mut score = 1;
+            for (dx, dy) in [(0, -1), (1, 0), (0, 1), (-1, 0)] {
+                let (mut cx, mut cy) = (x + dx, y + dy);
+                let mut distance = 0;
+                let mut blocked = false;
+                while (0..width).contains(&cx) && (0..height).contains(&cy) {
+                    distance += 1;
+                    if at(cx, cy) >= at(x, y) {
+                        blocked = true;
+                        break;
+                    }
+                    cx += dx;
+                    cy += dy;
+                }
+                seen |= !blocked;
+                score *= distance;
+            }
+            visible += seen as usize;
+            best = best.max(score);
+        }
+    }
+    Answers::new(visible, best)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    common::test_against_reference!(crate::Day8, 0..20usize);
+}
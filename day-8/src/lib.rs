@@ -0,0 +1,148 @@
+pub mod generate;
+
+use common::{Direction, Example, Grid, NoParams, ParseError, Solution, Vec2};
+use tracing::debug;
+
+/// The heights of the trees in the grove.
+pub struct HeightMap {
+    grid: Grid<u8>,
+}
+
+impl HeightMap {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::parse_lines(input.lines())
+    }
+
+    pub fn parse_lines<I, S>(lines: I) -> Result<Self, ParseError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let grid = Grid::parse_lines(lines, |c| c.to_digit(10).map(|d| d as u8))?;
+        Ok(HeightMap { grid })
+    }
+
+    pub fn height(&self, p: Vec2) -> u8 {
+        self.grid[p]
+    }
+
+    /// Whether the tree at `p` can be seen from outside the grove.
+    pub fn visible(&self, p: Vec2) -> bool {
+        let h = self.height(p);
+        Direction::ALL
+            .into_iter()
+            .any(|d| self.grid.ray(p, d.into()).all(|(_, tree)| *tree < h))
+    }
+
+    pub fn count_visible(&self) -> usize {
+        self.grid.positions().filter(|p| self.visible(*p)).count()
+    }
+
+    /// How many trees can be seen from `p` looking in `direction`.
+    pub fn viewing_distance(&self, p: Vec2, direction: Direction) -> usize {
+        let h = self.height(p);
+        let mut distance = 0;
+        for (_, tree) in self.grid.ray(p, direction.into()) {
+            distance += 1;
+            if *tree >= h {
+                break;
+            }
+        }
+        distance
+    }
+
+    pub fn scenic_score(&self, p: Vec2) -> usize {
+        Direction::ALL
+            .into_iter()
+            .map(|d| self.viewing_distance(p, d))
+            .product()
+    }
+}
+
+impl std::fmt::Display for HeightMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let digits = self
+            .grid
+            .display_with(|h| char::from_digit(*h as u32, 10).unwrap_or('?'));
+        f.write_fmt(format_args!("{}", digits))
+    }
+}
+
+const EXAMPLE: &str = "30373
+25512
+65332
+33549
+35390";
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Model = HeightMap;
+    type Params = NoParams;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "21", "8")];
+
+    fn parse_lines<I>(lines: I) -> anyhow::Result<HeightMap>
+    where
+        I: Iterator<Item = String>,
+    {
+        let hm = HeightMap::parse_lines(lines)?;
+        debug!(size = %hm.grid.size(), "parsed");
+        Ok(hm)
+    }
+
+    fn part1(hm: &HeightMap, _: &NoParams) -> anyhow::Result<usize> {
+        Ok(hm.count_visible())
+    }
+
+    fn part2(hm: &HeightMap, _: &NoParams) -> anyhow::Result<usize> {
+        let best = hm
+            .grid
+            .positions()
+            .map(|p| (hm.scenic_score(p), p))
+            .max_by_key(|(score, _)| *score);
+        debug!(?best, "most scenic tree");
+        Ok(best.map(|(score, _)| score).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let hm = HeightMap::parse(EXAMPLE)?;
+        assert_eq!(hm.count_visible(), 21);
+        Ok(())
+    }
+
+    #[test]
+    fn test2() -> anyhow::Result<()> {
+        let hm = HeightMap::parse(EXAMPLE)?;
+        assert_eq!(hm.scenic_score(Vec2::new(2, 1)), 4);
+        assert_eq!(hm.scenic_score(Vec2::new(2, 3)), 8);
+        assert_eq!(hm.scenic_score(Vec2::new(2, 0)), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_not_square() -> anyhow::Result<()> {
+        let hm = HeightMap::parse("3037\n2551\n6533")?;
+        assert_eq!(hm.to_string(), "3037\n2551\n6533\n");
+        assert_eq!(hm.height(Vec2::new(3, 1)), 1);
+        assert_eq!(hm.count_visible(), 12);
+        assert_eq!(hm.scenic_score(Vec2::new(1, 1)), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve() -> anyhow::Result<()> {
+        let answers = Day8::solve(EXAMPLE)?;
+        assert_eq!(answers.part1, "21");
+        assert_eq!(answers.part2, "8");
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+//! Random motions, and a slow but obviously correct solver to check them against.
+
+use common::Answers;
+use rand::Rng;
+use std::collections::HashSet;
+
+/// Between one and `size` motions of the head, each up to 10 steps.
+pub fn input<R: Rng + ?Sized>(rng: &mut R, size: usize) -> String {
+    (0..rng.random_range(1..=size.max(1)))
+        .map(|_| {
+            let direction = ["U", "D", "L", "R"][rng.random_range(0..4)];
+            format!("{} {}", direction, rng.random_range(1..=10))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Moves every knot that falls out of touch one step straight, or diagonally, towards the one
+/// ahead of it.
+fn tail_visits(input: &str, length: usize) -> usize {
+    let mut rope = vec![(0i32, 0i32); length];
+    let mut visits = HashSet::from([(0, 0)]);
+    for line in input.lines() {
+        let (direction, distance) = line.split_once(' ').unwrap();
+        let (dx, dy) = match direction {
+            "U" => (0, 1),
+            "D" => (0, -1),
+            "L" => (-1, 0),
+            _ => (1, 0),
+        };
+        for _ in 0..distance.parse::<usize>().unwrap() {
+            rope[0] = (rope[0].0 + dx, rope[0].1 + dy);
+            for knot in 1..length {
+                let (hx, hy) = rope[knot - 1];
+                let (tx, ty) = rope[knot];
+                if (hx - tx).abs() > 1 || (hy - ty).abs() > 1 {
+                    rope[knot] = (tx + (hx - tx).signum(), ty + (hy - ty).signum());
+                }
+            }
+            visits.insert(rope[length - 1]);
+        }
+    }
+    visits.len()
+}
+
+pub fn reference(input: &str) -> Answers {
+    Answers::new(tail_visits(input, 2), tail_visits(input, 10))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    common::test_against_reference!(crate::Day9, 0..100usize);
+}
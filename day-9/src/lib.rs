@@ -0,0 +1,206 @@
+pub mod generate;
+pub mod visualise;
+
+use common::{
+    parse::{parse_lines, parse_number},
+    vec2::{Direction, Vec2},
+    Example, NoParams, ParseError, ParseErrorKind, Solution,
+};
+use std::collections::HashSet;
+use tracing::{debug, trace};
+
+/// Where a knot at `tail` moves to after the knot ahead of it moves to `head`.
+pub fn tail_pos(head: &Vec2, tail: &Vec2) -> anyhow::Result<Vec2> {
+    let dir = tail.direction_to(head);
+    let diff = (*head - *tail).abs();
+
+    if (diff.x <= 2 && diff.y == 2) || (diff.y <= 2 && diff.x == 2) {
+        Ok(*tail + dir)
+    } else if diff.x >= 2 && diff.y >= 2 {
+        Err(anyhow::anyhow!("{:?} to {:?} is invalid", head, tail))
+    } else {
+        Ok(*tail)
+    }
+}
+
+/// Parses a line of the form "R 4" into a distance and a unit step.
+pub fn parse_line(line: &str) -> Result<(usize, Vec2), ParseError> {
+    let sp = line.split_once(' ');
+    if let Some((dir, dist)) = sp {
+        let dir = dir.parse::<Direction>().map_err(|e| e.within(line, dir))?;
+        return Ok((parse_number(line, dist)?, dir.into()));
+    }
+
+    Err(ParseError::in_line(
+        ParseErrorKind::Expected("a direction and distance"),
+        line,
+        line,
+    ))
+}
+
+/// Moves the head of `rope` one step in `direction`, and each knot after it to follow.
+pub fn step_rope(rope: &mut [Vec2], direction: Vec2) -> anyhow::Result<()> {
+    rope[0] += direction;
+    for knot in 1..rope.len() {
+        rope[knot] = tail_pos(&rope[knot - 1], &rope[knot])?;
+    }
+    Ok(())
+}
+
+/// How many positions the last knot of a rope of `length` knots visits.
+pub fn count_tail_visits(
+    start: &Vec2,
+    length: usize,
+    moves: &[(usize, Vec2)],
+) -> anyhow::Result<usize> {
+    let mut visits: HashSet<Vec2> = HashSet::new();
+
+    let mut rope = vec![*start; length];
+    for (distance, direction) in moves {
+        for _ in 0..*distance {
+            step_rope(&mut rope, *direction)?;
+            visits.insert(rope[length - 1]);
+        }
+        trace!(%direction, distance, head = %rope[0], tail = %rope[length - 1]);
+    }
+
+    debug!(length, visited = visits.len(), "tail visits");
+    Ok(visits.len())
+}
+
+const EXAMPLE: &str = "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2";
+
+/// A longer example for part 2, where the tail gets moving.
+const LARGER_EXAMPLE: &str = "R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20";
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    /// Each move of the head, as a distance and a unit direction
+    type Model = Vec<(usize, Vec2)>;
+    type Params = NoParams;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const EXAMPLES: &'static [Example] = &[
+        Example::new(EXAMPLE, "13", "1"),
+        Example::only_part2(LARGER_EXAMPLE, "36"),
+    ];
+
+    fn parse_lines<I>(lines: I) -> anyhow::Result<Self::Model>
+    where
+        I: Iterator<Item = String>,
+    {
+        let moves = parse_lines(lines, parse_line)?;
+        debug!(moves = moves.len(), "parsed");
+        Ok(moves)
+    }
+
+    fn part1(moves: &Self::Model, _: &NoParams) -> anyhow::Result<usize> {
+        count_tail_visits(&Vec2::origin(), 2, moves)
+    }
+
+    fn part2(moves: &Self::Model, _: &NoParams) -> anyhow::Result<usize> {
+        count_tail_visits(&Vec2::origin(), 10, moves)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let test_data = vec![
+            ((0, 0), (0, 0), (0, 0)),
+            ((0, 1), (0, 0), (0, 0)),
+            ((0, 2), (0, 0), (0, 1)),
+            ((1, 2), (1, 0), (1, 1)),
+            ((2, 2), (2, 0), (2, 1)),
+            ((3, 2), (2, 0), (3, 1)),
+        ];
+
+        for (head, tail, expected) in test_data {
+            let hv = {
+                let (x, y) = head;
+                Vec2 { x, y }
+            };
+            let tv = {
+                let (x, y) = tail;
+                Vec2 { x, y }
+            };
+            let ev = {
+                let (x, y) = expected;
+                Vec2 { x, y }
+            };
+            let r = tail_pos(&hv, &tv)?;
+            assert_eq!(r, ev, "{:?} {:?} expected {:?}, got {:?}", hv, tv, ev, r);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse() -> anyhow::Result<()> {
+        let test_data = vec![
+            ("U 1", (1, (0, 1))),
+            ("R 2", (2, (1, 0))),
+            ("D 3", (3, (0, -1))),
+            ("L 4", (4, (-1, 0))),
+        ];
+
+        for (input, (dir, v)) in test_data {
+            let ev = {
+                let (x, y) = v;
+                Vec2 { x, y }
+            };
+            assert_eq!(parse_line(input)?, (dir, ev));
+        }
+
+        let e = parse_line("X 4").unwrap_err();
+        assert_eq!((e.kind, e.column), (ParseErrorKind::Unexpected, 1));
+        let e = parse_line("U four").unwrap_err();
+        assert_eq!((e.kind, e.column), (ParseErrorKind::InvalidNumber, 3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_visit() -> anyhow::Result<()> {
+        let head = Vec2::new(0, 0);
+        let visits = count_tail_visits(&head, 2, &Day9::parse(EXAMPLE)?)?;
+        assert_eq!(visits, 13);
+        Ok(())
+    }
+
+    #[test]
+    fn test_visit_10() -> anyhow::Result<()> {
+        let head = Vec2::new(0, 0);
+        let visits = count_tail_visits(&head, 10, &Day9::parse(EXAMPLE)?)?;
+        assert_eq!(visits, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_visit_10_2() -> anyhow::Result<()> {
+        let head = Vec2::new(0, 0);
+        let visits = count_tail_visits(&head, 10, &Day9::parse(LARGER_EXAMPLE)?)?;
+        assert_eq!(visits, 36);
+        Ok(())
+    }
+}
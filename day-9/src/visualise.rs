@@ -0,0 +1,196 @@
+use crate::{step_rope, Day9};
+use common::{
+    image::{Draw, Picture, Rgb},
+    render::{Colour, Frame, Glyph, Part, Player, Visualise},
+    svg::{Style, Svg},
+    NoParams, Vec2,
+};
+use std::{collections::HashSet, io::Write};
+
+/// The rope and everywhere its tail has been. The puzzle's y points up, so rows are drawn
+/// flipped.
+pub struct RopeFrame<'a> {
+    pub rope: &'a [Vec2],
+    pub visited: &'a HashSet<Vec2>,
+    /// The corners of everywhere the head goes, which the rest of the rope never leaves
+    pub corners: (Vec2, Vec2),
+    pub caption: String,
+}
+
+/// Where position `p` of the puzzle is drawn, and the other way round.
+fn flip(p: Vec2) -> Vec2 {
+    Vec2::new(p.x, -p.y)
+}
+
+impl Frame for RopeFrame<'_> {
+    fn bounds(&self) -> (Vec2, Vec2) {
+        let (min, max) = self.corners;
+        (Vec2::new(min.x, -max.y), Vec2::new(max.x, -min.y))
+    }
+
+    fn glyph(&self, p: Vec2) -> Glyph {
+        let p = flip(p);
+        if let Some(knot) = self.rope.iter().position(|k| *k == p) {
+            return match knot {
+                0 => Glyph::coloured('H', Colour::Red),
+                _ if self.rope.len() == 2 => Glyph::coloured('T', Colour::Yellow),
+                k => {
+                    let digit = char::from_digit(k as u32 % 10, 10).unwrap();
+                    Glyph::coloured(digit, Colour::Yellow)
+                }
+            };
+        }
+        if p == Vec2::origin() {
+            Glyph::coloured('s', Colour::Green)
+        } else if self.visited.contains(&p) {
+            Glyph::coloured('#', Colour::Blue)
+        } else {
+            Glyph::coloured('.', Colour::Grey)
+        }
+    }
+
+    fn focus(&self) -> Option<Vec2> {
+        self.rope.first().map(|h| flip(*h))
+    }
+
+    fn caption(&self) -> String {
+        self.caption.clone()
+    }
+}
+
+/// How many knots the rope has in `part`.
+fn rope_length(part: Part) -> usize {
+    match part {
+        Part::One => 2,
+        Part::Two => 10,
+    }
+}
+
+impl Visualise for Day9 {
+    /// Shows the rope after each step of the head.
+    fn visualise<W: Write>(
+        moves: &Self::Model,
+        _: &NoParams,
+        part: Part,
+        player: &mut Player<W>,
+    ) -> anyhow::Result<()> {
+        let length = rope_length(part);
+
+        let mut corners = (Vec2::origin(), Vec2::origin());
+        let mut head = Vec2::origin();
+        for (distance, direction) in moves {
+            head += *direction * *distance as isize;
+            corners.0 = Vec2::new(corners.0.x.min(head.x), corners.0.y.min(head.y));
+            corners.1 = Vec2::new(corners.1.x.max(head.x), corners.1.y.max(head.y));
+        }
+
+        let mut rope = vec![Vec2::origin(); length];
+        let mut visited = HashSet::from([Vec2::origin()]);
+        for (i, (distance, direction)) in moves.iter().enumerate() {
+            for _ in 0..*distance {
+                step_rope(&mut rope, *direction)?;
+                visited.insert(rope[length - 1]);
+                player.show(&RopeFrame {
+                    rope: &rope,
+                    visited: &visited,
+                    corners,
+                    caption: format!(
+                        "Move {}/{}, tail visited {}",
+                        i + 1,
+                        moves.len(),
+                        visited.len()
+                    ),
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Where the middle of square `p` goes in a drawing: each square is two units across, so lines
+/// can run through the middles, and y is flipped.
+fn middle(p: Vec2) -> Vec2 {
+    Vec2::new(2 * p.x, -2 * p.y)
+}
+
+impl Draw for Day9 {
+    /// Draws every square the tail visits, the head's trail across them, and where the rope
+    /// ends up.
+    fn draw(moves: &Self::Model, _: &NoParams, part: Part) -> anyhow::Result<Picture> {
+        let mut rope = vec![Vec2::origin(); rope_length(part)];
+        let mut visited = HashSet::from([Vec2::origin()]);
+        let mut trail = vec![middle(Vec2::origin())];
+        for (distance, direction) in moves {
+            for _ in 0..*distance {
+                step_rope(&mut rope, *direction)?;
+                visited.insert(*rope.last().unwrap());
+            }
+            trail.push(middle(rope[0]));
+        }
+
+        let mut svg = Svg::new();
+        svg.background(Rgb::BLACK);
+        for p in &visited {
+            let square = Style::fill(Colour::Blue.into());
+            svg.rect(middle(*p) - Vec2::new(1, 1), Vec2::new(2, 2), square);
+        }
+        svg.polyline(&trail, Style::stroke(Colour::Grey.into(), 1.0))
+            .circle(
+                middle(Vec2::origin()),
+                0.8,
+                Style::fill(Colour::Green.into()),
+            );
+        let knots = rope.iter().map(|k| middle(*k)).collect::<Vec<_>>();
+        svg.polyline(&knots, Style::stroke(Colour::Yellow.into(), 2.0))
+            .circle(knots[0], 0.8, Style::fill(Colour::Red.into()));
+        Ok(Picture::Vector(svg))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::EXAMPLE;
+    use common::{render::Viewport, Solution};
+
+    #[test]
+    fn test_frame() {
+        let rope = [Vec2::new(2, 1), Vec2::new(1, 0)];
+        let visited = HashSet::from([Vec2::origin(), Vec2::new(1, 0)]);
+        let frame = RopeFrame {
+            rope: &rope,
+            visited: &visited,
+            corners: (Vec2::origin(), Vec2::new(3, 1)),
+            caption: String::new(),
+        };
+        assert_eq!(Viewport::default().render(&frame, false), "..H.\nsT..\n");
+    }
+
+    #[test]
+    fn test_draw() -> anyhow::Result<()> {
+        let moves = Day9::parse(EXAMPLE)?;
+        let Picture::Vector(svg) = Day9::draw(&moves, &NoParams {}, Part::One)? else {
+            panic!("expected shapes");
+        };
+        let drawn = svg.to_string();
+        assert_eq!(drawn.matches("<rect").count(), 1 + 13);
+        // The head's trail turns at the end of each move
+        assert!(
+            drawn.contains(r#"<polyline points="0,0 8,0 8,-8 2,-8 2,-6 10,-6 10,-4 0,-4 4,-4""#)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_visualise() -> anyhow::Result<()> {
+        let moves = Day9::parse(EXAMPLE)?;
+        let mut out = Vec::new();
+        let mut player = Player::new(&mut out, Viewport::default(), 0, false);
+        Day9::visualise(&moves, &NoParams {}, Part::One, &mut player)?;
+
+        let steps = moves.iter().map(|(d, _)| d).sum::<usize>();
+        assert_eq!(player.shown(), steps);
+        assert!(String::from_utf8(out)?.contains("Move 8/8, tail visited 13"));
+        Ok(())
+    }
+}
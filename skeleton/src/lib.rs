@@ -0,0 +1,48 @@
+use common::{Example, NoParams, Solution};
+use tracing::debug;
+
+const EXAMPLE: &str = "\
+first
+second
+";
+
+pub struct Skeleton;
+
+impl Solution for Skeleton {
+    type Model = Vec<String>;
+    type Params = NoParams;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "2", "0")];
+
+    fn parse_lines<I>(lines: I) -> anyhow::Result<Self::Model>
+    where
+        I: Iterator<Item = String>,
+    {
+        let lines = lines.collect::<Vec<_>>();
+        debug!(lines = lines.len(), "parsed");
+        Ok(lines)
+    }
+
+    fn part1(lines: &Self::Model, _: &NoParams) -> anyhow::Result<usize> {
+        Ok(lines.len())
+    }
+
+    fn part2(_lines: &Self::Model, _: &NoParams) -> anyhow::Result<usize> {
+        Ok(0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_example() -> anyhow::Result<()> {
+        let answers = Skeleton::solve(EXAMPLE)?;
+        assert_eq!(answers.part1, "2");
+        assert_eq!(answers.part2, "0");
+        Ok(())
+    }
+}
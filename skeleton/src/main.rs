@@ -1,19 +1,8 @@
+use common::Solution;
 use std::fs;
 
 fn main() -> anyhow::Result<()> {
     let input = fs::read_to_string("input")?;
-
-    let lines = input.lines();
-
+    print!("{}", skeleton::Skeleton::solve(&input)?);
     Ok(())
 }
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn test() {
-        assert!(true);
-    }
-}